@@ -1,25 +1,73 @@
-mod utils;
+pub mod utils;
 
-use anyhow::{Error, Result};
-use chrono::{Datelike, Local, NaiveDate};
-use clap::Parser;
-use itertools::izip;
+use ansi_term::Style;
+use anyhow::{bail, Error, Result};
+use chrono::{Datelike, Local, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+#[cfg(all(feature = "tui", unix))]
+use utils::browse_util::MonthBrowser;
+use utils::config_util::{config_path, parse_config, ConfigFile, ConfigValue};
 use utils::date_util::{
-    format_month, get_after_month, get_before_month, get_calendar, get_year_month,
-    is_all_same_year, parse_month,
+    count_business_days, count_weekend_days, days_in_month, first_of_month_offset,
+    format_agenda_line, format_days_between, format_iso_week, format_month, format_months_between,
+    format_ndjson_line, format_peek, format_summary, format_tsv_month, format_week,
+    format_weekday_count, format_weekend_count, format_year_label, format_year_progress,
+    get_after_month, get_before_month, get_year_month, is_all_same_year, is_highlighted,
+    is_leap_year, month_days, parse_date, parse_date_range, parse_localized_month,
+    parse_localized_weekday, parse_loose_date, parse_year_month, parse_year_range, visible_width,
+    week_rows, weekday_diff_days, weeks_since, FormatOptions, HeaderAlign, WeekColumn, WeekRule,
 };
+use utils::event_util::parse_events;
+use utils::frame_util::{frame_month, pad_month, FrameStyle, FRAME_EXTRA_WIDTH};
+use utils::hash_util::sha256_hex;
+use utils::heatmap_util::render_heatmap;
+use utils::locale_util::{parse_locale, Locale};
+use utils::persian_util::{format_persian_month, to_jalali};
+use utils::svg_util::render_svg;
+use utils::theme_util::Theme;
+#[cfg(all(feature = "tui", unix))]
+use utils::tui_util::browse;
 
 #[derive(Debug, Parser)]
-#[command(author, version, about)]
+#[command(author, version, about, disable_help_flag = true)]
 /// Rust version of `cal`
 struct Args {
     /// Year (1-9999)
     #[arg(value_parser(clap::value_parser!(i32).range(1..=9999)))]
     year: Option<i32>,
 
-    /// Month name or number (1-12)
-    #[arg(short)]
-    month: Option<String>,
+    /// Additional years to show in sequence after YEAR
+    /// (`MONTH YEAR` when the first value is 1-12 and exactly two are given)
+    #[arg(
+        value_name = "YEAR",
+        value_parser(clap::value_parser!(i32).range(1..=9999)),
+        requires("year")
+    )]
+    more_years: Vec<i32>,
+
+    /// Show a range of whole years in sequence (YYYY-YYYY)
+    #[arg(
+        long,
+        value_name = "YYYY-YYYY",
+        conflicts_with_all(["year", "month", "show_current_year", "three", "quarter", "from", "diff"])
+    )]
+    years: Option<String>,
+
+    /// Month name or number (1-12); a comma list shows only those months in calendar order
+    #[arg(short, value_delimiter = ',')]
+    month: Vec<String>,
+
+    /// Accept only month numbers and complete month names for -m (no prefixes)
+    #[arg(long, default_value_t = false)]
+    strict: bool,
 
     /// Show the whole current year
     #[arg(short('y'), long("year"), default_value_t = false, conflicts_with_all(["month", "year"]))]
@@ -28,86 +76,2177 @@ struct Args {
     /// Show near 3 month
     #[arg(short('3'), long, value_parser, default_value_t = false)]
     three: bool,
+
+    /// Show the month containing a date with that day highlighted
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        conflicts_with_all(["year", "month", "show_current_year", "quarter", "from", "fiscal_start"])
+    )]
+    around: Option<String>,
+
+    /// Show the three months of a calendar quarter (1-4)
+    #[arg(
+        long,
+        value_parser(clap::value_parser!(u32).range(1..=4)),
+        conflicts_with_all(["month", "three", "show_current_year"])
+    )]
+    quarter: Option<u32>,
+
+    /// First month of the fiscal year for the whole-year view (1-12)
+    #[arg(
+        long,
+        value_parser(clap::value_parser!(u32).range(1..=12)),
+        conflicts_with_all(["month", "three", "quarter"])
+    )]
+    fiscal_start: Option<u32>,
+
+    /// Show twelve months starting from the given (or current) month instead of January
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all(["three", "quarter", "fiscal_start", "from", "years", "calendar", "repeat", "dual", "diff", "from_day"])
+    )]
+    rolling_year: bool,
+
+    /// Draw a box-drawing border around each month
+    #[arg(long, default_value_t = false)]
+    ascii_box: bool,
+
+    /// Draw a border around only the month containing today in multi-month views
+    #[arg(long, default_value_t = false, conflicts_with_all(["ascii_box", "diff", "dual", "calendar"]))]
+    frame_today_month: bool,
+
+    /// Dim the previous and next months of the -3 view so the middle month stands out
+    #[arg(long, default_value_t = false, requires("three"))]
+    dim_adjacent: bool,
+
+    /// Format summary numbers (totals, percentages) with the digit grouping and decimal mark of --locale
+    #[arg(long, default_value_t = false, requires("locale"))]
+    locale_number: bool,
+
+    /// Leave out the weekday names line of each month
+    #[arg(long, default_value_t = false)]
+    no_weekday_header: bool,
+
+    /// Alignment of the month name above each grid
+    #[arg(long, value_enum, default_value_t = HeaderAlignName::Center)]
+    header_align: HeaderAlignName,
+
+    /// Highlight every day of the week containing today in the -3 view
+    #[arg(long, default_value_t = false, requires("three"))]
+    highlight_week: bool,
+
+    /// Use plain ASCII characters for the border (with --ascii-box)
+    #[arg(long, default_value_t = false, requires("ascii_box"))]
+    ascii: bool,
+
+    /// IANA timezone used to determine today (only affects which day is highlighted)
+    #[arg(long, value_name = "IANA")]
+    timezone: Option<String>,
+
+    /// Count occurrences of a weekday (name or prefix) instead of printing the calendar
+    #[arg(long, value_name = "WEEKDAY")]
+    count_weekday: Option<String>,
+
+    /// First month of a range (YYYY-MM)
+    #[arg(
+        long,
+        value_name = "YYYY-MM",
+        requires("to"),
+        conflicts_with_all(["month", "year", "show_current_year", "three"])
+    )]
+    from: Option<String>,
+
+    /// Last month of a range (YYYY-MM)
+    #[arg(long, value_name = "YYYY-MM", requires("from"))]
+    to: Option<String>,
+
+    /// Print the selected months as YYYY-MM lines instead of the calendar
+    #[arg(long, default_value_t = false)]
+    list_months: bool,
+
+    /// Print the number of days covered instead of the calendar
+    #[arg(long, default_value_t = false)]
+    summary: bool,
+
+    /// Print the number of Monday-Friday days covered, excluding --events dates, instead of the calendar
+    #[arg(long, default_value_t = false)]
+    business_days: bool,
+
+    /// Print the number of Saturday and Sunday days in each month, with a total for several months
+    #[arg(long, default_value_t = false)]
+    weekends: bool,
+
+    /// Drop trailing blank week rows (single month only)
+    #[arg(long, default_value_t = false)]
+    compact: bool,
+
+    /// Omit the year header above multi-month views
+    #[arg(long, default_value_t = false)]
+    no_year: bool,
+
+    /// Append "(leap year)" to the year header of a leap year
+    #[arg(long, default_value_t = false)]
+    show_leap: bool,
+
+    /// Print today's position within its year beneath a full-year calendar
+    #[arg(long, default_value_t = false)]
+    year_progress: bool,
+
+    /// Order months right-to-left within each row
+    #[arg(long, default_value_t = false)]
+    rtl: bool,
+
+    /// String placed between months in a row (e.g. " | ")
+    #[arg(long, value_name = "STRING")]
+    separator: Option<String>,
+
+    /// String placed between the days of a week (e.g. "·")
+    #[arg(long, value_name = "STRING", default_value = " ")]
+    day_separator: String,
+
+    /// Draw a horizontal rule of dashes between rows of months
+    #[arg(long)]
+    grid_lines: bool,
+
+    /// Number of months per row (defaults to fitting $COLUMNS on a terminal, otherwise at most 3)
+    #[arg(long, value_parser(clap::value_parser!(u32).range(1..)))]
+    months_per_row: Option<u32>,
+
+    /// Use three-letter weekday headers with wider day columns
+    #[arg(long, default_value_t = false)]
+    wide_weekdays: bool,
+
+    /// Blank out the days before this day of a single month
+    #[arg(
+        long,
+        value_name = "DAY",
+        value_parser(clap::value_parser!(u32).range(1..=31)),
+        conflicts_with_all(["three", "quarter", "fiscal_start", "from", "years"])
+    )]
+    from_day: Option<u32>,
+
+    /// Render the selected single month this many times side by side
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser(clap::value_parser!(u32).range(1..)),
+        conflicts_with_all(["three", "quarter", "fiscal_start", "from", "years", "calendar"])
+    )]
+    repeat: Option<u32>,
+
+    /// Hide the Saturday and Sunday columns, leaving a five-column Monday-Friday grid
+    #[arg(long, default_value_t = false, conflicts_with("week"))]
+    collapse_weekends: bool,
+
+    /// Print years as four digits with leading zeros (e.g. 0044) in headers and TSV output
+    #[arg(long, default_value_t = false)]
+    zero_pad_year: bool,
+
+    /// Label years before 1 astronomically (0, -1, ...) in headers instead of 1 BC, 2 BC, ...
+    #[arg(long, default_value_t = false)]
+    astronomical_year: bool,
+
+    /// Append the moon phase at the middle of each week to its row
+    #[arg(long, default_value_t = false)]
+    moon: bool,
+
+    /// Number the days from 1 at the start of the range instead of per month (e.g. with --from/--to)
+    #[arg(long, default_value_t = false, conflicts_with_all(["calendar", "diff", "dual", "week", "peek"]))]
+    continuous_days: bool,
+
+    /// Show the months in reverse order, from the last month back to the first
+    #[arg(long, default_value_t = false, conflicts_with("calendar"))]
+    reverse_months: bool,
+
+    /// Show month numbers in Roman numerals (I-XII) instead of month names in headers
+    #[arg(long, default_value_t = false)]
+    roman: bool,
+
+    /// Leave a blank line between the weekday header and the first week
+    #[arg(long, default_value_t = false)]
+    header_gap: bool,
+
+    /// Minimum width of each month block (wider blocks are padded on the right)
+    #[arg(long, value_name = "COLUMNS")]
+    min_width: Option<usize>,
+
+    /// Decorate the first and last day of each month (today takes precedence)
+    #[arg(long, default_value_t = false)]
+    mark_boundaries: bool,
+
+    /// Put the month name below the weeks instead of above the weekday header
+    #[arg(long, default_value_t = false, conflicts_with("ascii_box"))]
+    invert: bool,
+
+    /// Highlight additional dates (comma separated YYYY-MM-DD)
+    #[arg(long, value_name = "YYYY-MM-DD", value_delimiter = ',')]
+    highlight: Vec<String>,
+
+    /// Highlight inclusive spans of days (comma separated YYYY-MM-DD:YYYY-MM-DD)
+    #[arg(long, value_name = "YYYY-MM-DD:YYYY-MM-DD", value_delimiter = ',')]
+    highlight_range: Vec<String>,
+
+    /// Highlight every occurrence of the given weekdays (comma separated names or prefixes)
+    #[arg(long, value_name = "WEEKDAYS", value_delimiter = ',')]
+    recur: Vec<String>,
+
+    /// Underline dates listed in a file of "YYYY-MM-DD description" lines
+    #[arg(long, value_name = "FILE")]
+    events: Option<String>,
+
+    /// List each day of the span as "Mon 2024-03-04" instead of the calendar
+    #[arg(long, default_value_t = false)]
+    agenda: bool,
+
+    /// Print the number of week rows of each month instead of the calendar
+    #[arg(long, default_value_t = false)]
+    weeks_count: bool,
+
+    /// Show a month of two years side by side, highlighting days whose weekday differs
+    #[arg(
+        long,
+        value_name = "YEAR,YEAR",
+        value_delimiter = ',',
+        value_parser(clap::value_parser!(i32).range(1..=9999)),
+        conflicts_with_all(["year", "show_current_year", "three", "quarter", "from", "fiscal_start"])
+    )]
+    diff: Vec<i32>,
+
+    /// Show a single Gregorian month next to the month of another calendar containing its 1st
+    #[arg(
+        long,
+        value_enum,
+        value_name = "CALENDAR",
+        conflicts_with_all(["calendar", "diff", "three", "quarter", "from", "fiscal_start", "years", "repeat"])
+    )]
+    dual: Option<CalendarKind>,
+
+    /// Show only the week containing today
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all(["month", "year", "show_current_year", "three", "quarter", "from"])
+    )]
+    week: bool,
+
+    /// Show only the weekday header and the row of this month containing today
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all(["month", "year", "show_current_year", "three", "quarter", "from", "week", "collapse_weekends"])
+    )]
+    peek: bool,
+
+    /// Print the number of days between two dates instead of the calendar
+    #[arg(long, num_args = 2, value_names = ["YYYY-MM-DD", "YYYY-MM-DD"])]
+    between: Vec<String>,
+
+    /// Print the number of months between two months instead of the calendar
+    #[arg(long, num_args = 2, value_names = ["YYYY-MM", "YYYY-MM"], conflicts_with("between"))]
+    months_between: Vec<String>,
+
+    /// Print the ISO week date (YYYY-Www-D) of a date instead of the calendar
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    iso_week: Option<String>,
+
+    /// Print the ISO week-numbering year of a date (may differ from the calendar year)
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    iso_year: Option<String>,
+
+    /// Print today's date (honoring CALR_TODAY and --timezone) instead of the calendar
+    #[arg(long, default_value_t = false)]
+    print_today: bool,
+
+    /// Locale for month and weekday names (en, de, es, fr; e.g. fr-CA)
+    #[arg(long, global = true)]
+    locale: Option<String>,
+
+    /// First day of the week (weekday name, or "auto" to follow --locale)
+    #[arg(long, value_name = "WEEKDAY|auto")]
+    first_day: Option<String>,
+
+    /// Start weeks on Saturday (same as --first-day saturday)
+    #[arg(long, default_value_t = false, conflicts_with_all(["first_day", "iso"]))]
+    saturday: bool,
+
+    /// ISO 8601 conventions: weeks start on Monday and show ISO week numbers
+    #[arg(long, default_value_t = false, conflicts_with_all(["first_day", "debug_columns"]))]
+    iso: bool,
+
+    /// Prefix each week row with its week number within the month (1-6)
+    #[arg(long, default_value_t = false, conflicts_with_all(["iso", "debug_columns"]))]
+    week_of_month: bool,
+
+    /// Prefix each week row with its week number, counted by this rule
+    #[arg(long, value_enum, conflicts_with_all(["week_of_month", "debug_columns"]))]
+    week_rule: Option<WeekRuleName>,
+
+    /// Prefix each week row with the number of weeks since this date (its week is 0)
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        conflicts_with_all(["iso", "week_rule", "week_of_month", "debug_columns"])
+    )]
+    week_epoch: Option<String>,
+
+    /// Label years with an era name in headers (the grid stays Gregorian)
+    #[arg(long, value_enum, value_name = "ERA")]
+    era: Option<EraKind>,
+
+    /// Prefix each week row with the weekday index of its first day (debugging aid)
+    #[arg(long, hide = true, default_value_t = false)]
+    debug_columns: bool,
+
+    /// Color theme for today, weekends, headers and week numbers
+    #[arg(long, value_enum, default_value_t = ThemeName::Default)]
+    theme: ThemeName,
+
+    /// Text colors per category, e.g. today=red,events=green,weekends=blue (overrides the theme)
+    #[arg(long, value_name = "CATEGORY=COLOR,...")]
+    style: Option<String>,
+
+    /// Use #rrggbb colors of --style as 24-bit colors (otherwise they are reduced to basic colors)
+    #[arg(long, default_value_t = false, requires("style"))]
+    truecolor: bool,
+
+    /// How to mark today: the theme's decoration, brackets or an underline
+    #[arg(long, value_enum, default_value_t = TodayStyle::Reverse)]
+    today_style: TodayStyle,
+
+    /// Link each day to a URL on terminals ("{date}" becomes YYYY-MM-DD)
+    #[arg(long, value_name = "URL-TEMPLATE")]
+    link_format: Option<String>,
+
+    /// Digits used for the day numbers
+    #[arg(long, value_enum, default_value_t = Digits::Latin)]
+    digits: Digits,
+
+    /// When to use colors and other decorations
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Do not highlight today (as with BSD cal)
+    #[arg(short = 'h', long, default_value_t = false)]
+    no_highlight_today: bool,
+
+    /// Print help
+    #[arg(long, action = clap::ArgAction::Help)]
+    help: Option<bool>,
+
+    /// Calendar system; year and month arguments are taken in this calendar
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = CalendarKind::Gregorian,
+        conflicts_with_all(["quarter", "fiscal_start", "from", "diff", "week", "around"])
+    )]
+    calendar: CalendarKind,
+
+    /// Output format of the calendar
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Write the calendar to the file instead of standard output
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<String>,
+
+    /// Write each month to its own file DIR/YYYY-MM.<format> instead of standard output
+    #[arg(long, value_name = "DIR", conflicts_with_all(["output", "quiet"]))]
+    split_output: Option<String>,
+
+    /// Browse month by month in a full-screen view (arrow keys, t for today, q to quit)
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all(["output", "split_output", "quiet", "checksum"])
+    )]
+    interactive: bool,
+
+    /// Print the SHA-256 hash of the undecorated calendar instead of the calendar
+    #[arg(long, default_value_t = false, conflicts_with_all(["quiet", "split_output"]))]
+    checksum: bool,
+
+    /// Print nothing; only report through the exit status whether the arguments are valid
+    #[arg(short, long, default_value_t = false, conflicts_with("output"))]
+    quiet: bool,
+
+    /// Check the arguments, dates and ranges, then exit without rendering anything
+    #[arg(long, default_value_t = false)]
+    validate_only: bool,
+
+    /// Prefix every output line with this many spaces
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    indent: usize,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
 }
 
-pub fn run() -> Result<String> {
+/// 配色の名前
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ThemeName {
+    /// Reverse video for today and highlights
+    Default,
+    /// Solarized colors (256-color terminals)
+    Solarized,
+    /// Attributes only, no colors
+    Mono,
+}
+
+/// 当日の示し方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TodayStyle {
+    /// The theme's decoration (reverse video by default)
+    Reverse,
+    /// Brackets around the day, e.g. [ 7]
+    Bracket,
+    /// Underlined day
+    Underline,
+}
+
+/// 日付の数字の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Digits {
+    /// Latin digits (0-9)
+    Latin,
+    /// Arabic-Indic digits (٠-٩)
+    Arabic,
+}
+
+/// 装飾を行うか否か
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    /// Decorate the output only when writing to a terminal
+    Auto,
+    /// Always decorate the output
+    Always,
+    /// Never decorate the output
+    Never,
+}
+
+/// 月ヘッダの寄せ方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HeaderAlignName {
+    /// Left-aligned
+    Left,
+    /// Centered
+    Center,
+    /// Right-aligned
+    Right,
+}
+
+/// 週番号の数え方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum WeekRuleName {
+    /// ISO 8601: week 1 contains the first Thursday
+    Iso,
+    /// Week 1 contains January 1
+    Jan1,
+    /// Week 1 is the first full week of the year
+    FirstFull,
+}
+
+/// 年の表記に用いる元号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum EraKind {
+    /// Japanese era (e.g. 令和6年)
+    Japanese,
+}
+
+/// 暦法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CalendarKind {
+    /// Gregorian calendar
+    Gregorian,
+    /// Persian (Jalali) calendar
+    Persian,
+}
+
+/// カレンダーの出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Plain text grid
+    Text,
+    /// SVG image of the month grid
+    Svg,
+    /// Newline-delimited JSON, one record per day
+    Ndjson,
+    /// The whole year as one character per day, weeks as columns
+    Heatmap,
+    /// Tab-separated weeks, one header row per month
+    Tsv,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// List the month and weekday names recognized for a locale
+    Names,
+    /// Validate a loosely written date (e.g. 2024-3-4, 2024/03/04) and print it as YYYY-MM-DD
+    Parse {
+        /// Date to validate
+        date: String,
+    },
+    /// Print the weekday name of a date (YYYY-MM-DD), localized with --locale
+    Weekday {
+        /// Date to look up
+        date: String,
+    },
+}
+
+/// コマンドライン引数の解析結果
+#[derive(Clone)]
+struct Config {
+    year: Option<i32>,
+    years: Vec<i32>,
+    month: Option<u32>,
+    months: Vec<u32>,
+    today: NaiveDate,
+    three: bool,
+    quarter: Option<u32>,
+    fiscal_start: u32,
+    frame: Option<FrameStyle>,
+    frame_today_month: bool,
+    dim_adjacent: bool,
+    count_weekday: Option<Weekday>,
+    span: Option<((i32, u32), (i32, u32))>,
+    list_months: bool,
+    summary: bool,
+    business_days: bool,
+    weekends: bool,
+    compact: bool,
+    no_year: bool,
+    show_leap: bool,
+    year_progress: bool,
+    iso_week: Option<NaiveDate>,
+    iso_year: Option<NaiveDate>,
+    print_today: bool,
+    between: Option<(NaiveDate, NaiveDate)>,
+    months_between: Option<((i32, u32), (i32, u32))>,
+    week: bool,
+    reverse_months: bool,
+    peek: bool,
+    continuous_days: bool,
+    repeat: usize,
+    layout: Layout,
+    agenda: bool,
+    weeks_count: bool,
+    diff: Option<(i32, i32)>,
+    dual: Option<CalendarKind>,
+    names: bool,
+    parsed_date: Option<NaiveDate>,
+    weekday_of: Option<NaiveDate>,
+    format: OutputFormat,
+    output: Option<String>,
+    split_output: Option<String>,
+    interactive: bool,
+    checksum: bool,
+    quiet: bool,
+    validate_only: bool,
+    indent: usize,
+    calendar: CalendarKind,
+    opts: FormatOptions,
+}
+
+/// 月同士の既定の区切り(各月の末尾にも付与する)
+const DEFAULT_SEPARATOR: &str = "  ";
+
+/// 複数月を並べて出力する際のレイアウト設定
+#[derive(Debug, Clone)]
+struct Layout {
+    /// 1行に並べる月数
+    months_per_row: usize,
+    /// 各行の月を右から左に並べるか否か
+    rtl: bool,
+    /// 月同士の区切り(未指定の場合は既定の区切りを各月の末尾に付与する)
+    separator: Option<String>,
+    /// 月の行同士の間に罫線を引くか否か
+    grid_lines: bool,
+}
+
+impl Layout {
+    /// 月同士の区切り
+    fn gap(&self) -> &str {
+        self.separator.as_deref().unwrap_or(DEFAULT_SEPARATOR)
+    }
+
+    /// 各行の末尾に付与する文字列(区切りを指定した場合は付与しない)
+    fn trailing(&self) -> &str {
+        match self.separator {
+            Some(_) => "",
+            None => DEFAULT_SEPARATOR,
+        }
+    }
+}
+
+/// 各行の先頭に空白を付与する出力先(空行には付与しない)
+struct IndentWriter<'a> {
+    /// 元の出力先
+    inner: &'a mut dyn Write,
+    /// 各行の先頭に付与する空白
+    indent: String,
+    /// 次に書き込む位置が行頭か否か
+    line_start: bool,
+}
+
+impl<'a> IndentWriter<'a> {
+    /// 出力先と空白の桁数から生成
+    ///
+    /// * `inner`  - 元の出力先
+    /// * `indent` - 空白の桁数
+    fn new(inner: &'a mut dyn Write, indent: usize) -> Self {
+        IndentWriter {
+            inner,
+            indent: " ".repeat(indent),
+            line_start: true,
+        }
+    }
+}
+
+impl Write for IndentWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&byte| byte == b'\n') {
+            if self.line_start && line != b"\n" {
+                self.inner.write_all(self.indent.as_bytes())?;
+            }
+            self.inner.write_all(line)?;
+            self.line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// カレンダーを出力先に書き込む
+///
+/// * `out` - 出力先
+pub fn run(out: &mut dyn Write) -> Result<String> {
     // コマンドライン引数解析
-    let (year, month, today, three_flg) = parse_args()?;
+    let config = parse_args()?;
+
+    // 「--validate-only」の場合は引数・日付・範囲の検証のみで終了する
+    if config.validate_only {
+        return Ok(String::from("Success"));
+    }
+
+    // 「--interactive」の場合は月を切り替える画面を開く
+    if config.interactive {
+        run_interactive(&config)?;
+        return Ok(String::from("Success"));
+    }
+
+    // 「--split-output」の指定がある場合は1ヶ月毎にファイルへ出力する
+    if let Some(ref dir) = config.split_output {
+        write_split_output(dir, &config)?;
+        return Ok(String::from("Success"));
+    }
+
+    // 「--output」の指定がある場合は出力先をファイルに切り替える
+    // 「--quiet」の場合は出力を破棄する(引数の検証とカレンダーの生成は行う)
+    let mut file;
+    let mut sink = io::sink();
+    let out: &mut dyn Write = match config.output {
+        _ if config.quiet => &mut sink,
+        Some(ref path) => {
+            file = match fs::File::create(path) {
+                Ok(f) => BufWriter::new(f),
+                Err(e) => bail!("{path}: {e}"),
+            };
+            &mut file
+        }
+        None => out,
+    };
+    // 「--checksum」の場合はカレンダーを一旦バッファに書き込み、ハッシュ値のみ出力する
+    let checksum = config.checksum;
+    let mut rendered = Vec::new();
+    {
+        let out: &mut dyn Write = if checksum { &mut rendered } else { &mut *out };
+        // 「--indent」の指定がある場合は各行の先頭に空白を付与する
+        let mut indented = IndentWriter::new(out, config.indent);
+        let out: &mut dyn Write = &mut indented;
+
+        // 複数の年が指定された場合は1年ずつ空行を挟んで出力
+        if config.years.len() > 1 {
+            for (i, &year) in config.years.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                render(
+                    out,
+                    Config {
+                        year: Some(year),
+                        ..config.clone()
+                    },
+                )?;
+            }
+        } else {
+            render(out, config)?;
+        }
+        out.flush()?;
+    }
+    if checksum {
+        writeln!(out, "{}", sha256_hex(&rendered))?;
+        out.flush()?;
+    }
+
+    Ok(String::from("Success"))
+}
+
+/// 月を切り替える画面を開く(対象月、指定がなければ当日の月から表示する)
+///
+/// * `config` - コマンドライン引数の解析結果
+#[cfg(all(feature = "tui", unix))]
+fn run_interactive(config: &Config) -> Result<()> {
+    let today = config.today;
+    let browser = MonthBrowser::new(
+        config.year.unwrap_or(today.year()),
+        config.month.unwrap_or(today.month()),
+        today,
+    );
+    browse(browser, today, &config.opts)
+}
+
+/// 月を切り替える画面を開く(「tui」フィーチャーなしでビルドした場合は引数の解析でエラーとなる)
+///
+/// * `config` - コマンドライン引数の解析結果
+#[cfg(not(all(feature = "tui", unix)))]
+fn run_interactive(_config: &Config) -> Result<()> {
+    unreachable!("--interactive is rejected by parse_args without the tui feature")
+}
+
+/// 対象の月を1ヶ月毎にディレクトリ内のファイル(`YYYY-MM.txt`など)へ書き込む
+/// 対象の月は「--list-months」と同じ方法で求め、各月を単月のカレンダーとして出力する。
+///
+/// * `dir`    - 出力先のディレクトリ(存在しない場合は作成する)
+/// * `config` - コマンドライン引数の解析結果
+fn write_split_output(dir: &str, config: &Config) -> Result<()> {
+    let extension = match config.format {
+        OutputFormat::Text => "txt",
+        OutputFormat::Svg => "svg",
+        OutputFormat::Ndjson => "ndjson",
+        OutputFormat::Tsv => "tsv",
+        // 「--format heatmap」との組み合わせは引数の解析でエラーとなる
+        OutputFormat::Heatmap => unreachable!("--split-output with --format heatmap"),
+    };
+    if let Err(e) = fs::create_dir_all(dir) {
+        bail!("{dir}: {e}");
+    }
+
+    // 対象の年月を取得(複数の年が指定された場合は全ての年)
+    let years = match config.years[..] {
+        [_, _, ..] => config.years.iter().map(|&year| Some(year)).collect(),
+        _ => vec![config.year],
+    };
+    let mut listed = Vec::new();
+    for year in years {
+        let config = Config {
+            year,
+            list_months: true,
+            ..config.clone()
+        };
+        render(&mut listed, config)?;
+    }
+
+    for line in String::from_utf8(listed)?.lines() {
+        let (year, month) = parse_year_month(line.to_string())?;
+        let path = Path::new(dir).join(format!("{line}.{extension}"));
+        let mut file = match fs::File::create(&path) {
+            Ok(f) => BufWriter::new(f),
+            Err(e) => bail!("{}: {e}", path.display()),
+        };
+        let mut out = IndentWriter::new(&mut file, config.indent);
+        let config = Config {
+            year: Some(year),
+            month: Some(month),
+            months: vec![month],
+            three: false,
+            quarter: None,
+            span: Some(((year, month), (year, month))),
+            repeat: 1,
+            ..config.clone()
+        };
+        render(&mut out, config)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// 引数の解析結果に従ってカレンダーを出力先に書き込む
+///
+/// * `out`    - 出力先
+/// * `config` - コマンドライン引数の解析結果
+fn render(out: &mut dyn Write, config: Config) -> Result<()> {
+    let Config {
+        year,
+        years: _,
+        month,
+        months,
+        today,
+        three: three_flg,
+        quarter,
+        fiscal_start,
+        frame,
+        frame_today_month,
+        dim_adjacent,
+        count_weekday,
+        span,
+        list_months,
+        summary,
+        business_days,
+        weekends,
+        compact,
+        no_year,
+        show_leap,
+        year_progress,
+        iso_week,
+        iso_year,
+        print_today,
+        between,
+        months_between,
+        week,
+        reverse_months,
+        peek,
+        continuous_days,
+        repeat,
+        layout,
+        agenda,
+        weeks_count,
+        diff,
+        dual,
+        names,
+        parsed_date,
+        weekday_of,
+        format,
+        output: _,
+        split_output: _,
+        interactive: _,
+        checksum: _,
+        quiet: _,
+        validate_only: _,
+        indent: _,
+        calendar,
+        opts,
+    } = config;
+
+    // オプション「--print-today」の処理
+    if print_today {
+        writeln!(out, "{}", today.format("%Y-%m-%d"))?;
+        return Ok(());
+    }
+
+    // サブコマンド「parse」の処理
+    if let Some(date) = parsed_date {
+        writeln!(out, "{}", date.format("%Y-%m-%d"))?;
+        return Ok(());
+    }
+
+    // サブコマンド「weekday」の処理
+    if let Some(date) = weekday_of {
+        let name = opts.locale.weekday_names()[date.weekday().num_days_from_sunday() as usize];
+        writeln!(out, "{name}")?;
+        return Ok(());
+    }
+
+    // サブコマンド「names」の処理
+    if names {
+        writeln!(out, "Months")?;
+        for name in opts.locale.month_names() {
+            writeln!(out, "  {name}")?;
+        }
+        writeln!(out, "Weekdays")?;
+        for name in opts.locale.weekday_names() {
+            writeln!(out, "  {name}")?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--between」の処理
+    if let Some((from, to)) = between {
+        writeln!(out, "{}", format_days_between(from, to, &opts))?;
+        return Ok(());
+    }
+
+    // オプション「--months-between」の処理
+    if let Some((from, to)) = months_between {
+        writeln!(out, "{}", format_months_between(from, to, &opts))?;
+        return Ok(());
+    }
+
+    // オプション「--iso-week」の処理
+    if let Some(date) = iso_week {
+        writeln!(out, "{}", format_iso_week(date))?;
+        return Ok(());
+    }
+
+    // オプション「--iso-year」の処理
+    if let Some(date) = iso_year {
+        writeln!(out, "{}", date.iso_week().year())?;
+        return Ok(());
+    }
+
+    // オプション「--week」の処理
+    if week {
+        let lines = format_week(today, &opts);
+        let width = block_width(&lines);
+        for line in lines {
+            writeln!(out, "{line}{}", gap_after(&line, width, layout.trailing()))?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--peek」の処理
+    if peek {
+        for line in format_peek(today, &opts) {
+            writeln!(out, "{line}{}", layout.trailing())?;
+        }
+        return Ok(());
+    }
+
     let year = year.unwrap_or(today.year());
 
-    // オプション「-3」の処理
-    if three_flg {
+    // オプション「--calendar=persian」の処理
+    if calendar == CalendarKind::Persian {
+        let year_months: Vec<_> = if three_flg {
+            let month = month.unwrap_or(to_jalali(today).1);
+            (-1..=1)
+                .map(|delta| first_of_month_offset(year, month, delta))
+                .collect()
+        } else if let Some(month) = month {
+            vec![(year, month)]
+        } else {
+            (1..=12).map(|month| (year, month)).collect()
+        };
+        let all_same_year = is_all_same_year(year_months.clone());
+        let calendar: Vec<_> = year_months
+            .iter()
+            .map(|&(year, month)| {
+                format_persian_month(
+                    year,
+                    month,
+                    year_months.len() == 1 || !all_same_year,
+                    today,
+                    &opts,
+                )
+            })
+            .collect();
+        if year_months.len() == 1 {
+            for line in apply_frame(calendar, frame, opts.header_align).concat() {
+                writeln!(out, "{line}{}", layout.trailing())?;
+            }
+        } else {
+            if all_same_year && !no_year {
+                let year_width = year_header_width(
+                    month_width(frame, &opts),
+                    layout.months_per_row,
+                    layout.gap().chars().count(),
+                );
+                writeln!(
+                    out,
+                    "{}",
+                    format_year_header(&opts.format_year(year), year_width)
+                )?;
+            }
+            print_chunk_tree_month(
+                out,
+                apply_frame(calendar, frame, opts.header_align),
+                &layout,
+            )?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--diff」の処理
+    if let Some((year_a, year_b)) = diff {
         let month = month.unwrap_or(today.month());
+        let calendar: Vec<_> = [(year_a, year_b), (year_b, year_a)]
+            .into_iter()
+            .map(|(year, other_year)| {
+                // 「--highlight」などで指定した日付も合わせて強調表示する
+                let mut highlights = weekday_diff_days(month, year, other_year);
+                highlights.extend(&opts.highlights);
+                let opts = FormatOptions {
+                    highlights,
+                    ..opts.clone()
+                };
+                format_month(year, month, true, today, &opts)
+            })
+            .collect();
+        print_chunk_tree_month(
+            out,
+            apply_frame(calendar, frame, opts.header_align),
+            &layout,
+        )?;
+        writeln!(
+            out,
+            "Highlighted days fall on a different weekday in {year_a} and {year_b}"
+        )?;
+        return Ok(());
+    }
 
-        // 対象とする期間を決定(前後１ヶ月)
-        let start_date = get_before_month(1, year, month);
-        let end_date = get_after_month(1, year, month);
-        let year_months = get_year_month(start_date, end_date);
+    // オプション「--dual」の処理(グレゴリオ暦の月と、その月の1日を含む別の暦の月)
+    if let Some(dual) = dual {
+        let month = month.unwrap_or(today.month());
+        let gregorian = format_month(year, month, true, today, &opts);
+        let other = match dual {
+            CalendarKind::Gregorian => gregorian.clone(),
+            CalendarKind::Persian => {
+                let (year, month, _) = to_jalali(NaiveDate::from_ymd_opt(year, month, 1).unwrap());
+                format_persian_month(year, month, true, today, &opts)
+            }
+        };
+        print_chunk_tree_month(
+            out,
+            apply_frame(vec![gregorian, other], frame, opts.header_align),
+            &layout,
+        )?;
+        return Ok(());
+    }
 
-        // すべての年が同じか確認
-        let all_same_year = is_all_same_year(year_months.clone());
-        if all_same_year {
-            println!("{year:>32}");
+    // 対象とする期間を決定
+    let (start_date, end_date) = if let Some(((from_year, from_month), (to_year, to_month))) = span
+    {
+        // オプション「--from」「--to」の処理
+        (
+            get_before_month(0, from_year, from_month),
+            get_after_month(0, to_year, to_month),
+        )
+    } else if let Some(quarter) = quarter {
+        // オプション「--quarter」の処理(四半期の３ヶ月)
+        let first_month = (quarter - 1) * 3 + 1;
+        (
+            get_before_month(0, year, first_month),
+            get_after_month(2, year, first_month),
+        )
+    } else if three_flg {
+        // オプション「-3」の処理(前後１ヶ月)
+        let month = month.unwrap_or(today.month());
+        (
+            get_before_month(1, year, month),
+            get_after_month(1, year, month),
+        )
+    } else if let [first_month, .., last_month] = months[..] {
+        // オプション「-m」に複数の月が指定された場合(先頭月〜末尾月)
+        (
+            get_before_month(0, year, first_month),
+            get_after_month(0, year, last_month),
+        )
+    } else if let Some(month) = month {
+        // 対象月のみ
+        (
+            get_before_month(0, year, month),
+            get_after_month(0, year, month),
+        )
+    } else {
+        // 対象年1年間(年度開始月から12ヶ月)
+        (
+            get_before_month(0, year, fiscal_start),
+            get_after_month(11, year, fiscal_start),
+        )
+    };
+    // 起点日からの週数の列は期間の最大桁数に揃える(オプション「--week-epoch」)
+    let week_column = match opts.week_column {
+        Some(WeekColumn::WeeksSince { epoch, .. }) => Some(WeekColumn::WeeksSince {
+            epoch,
+            digits: [start_date, end_date]
+                .iter()
+                .map(|&date| weeks_since(epoch, date, opts.first_day).to_string().len())
+                .max()
+                .unwrap_or_default(),
+        }),
+        week_column => week_column,
+    };
+    // 日付を期間の開始日からの通し番号で表示(オプション「--continuous-days」)
+    let opts = FormatOptions {
+        continuous_days: continuous_days.then_some((start_date, end_date)),
+        week_column,
+        ..opts
+    };
+    let year_width = year_header_width(
+        month_width(frame, &opts),
+        layout.months_per_row,
+        layout.gap().chars().count(),
+    );
+
+    let mut year_months: Vec<_> = if months.len() > 1 {
+        months.iter().map(|&month| (year, month)).collect()
+    } else {
+        // オプション「--repeat」の場合は同じ月を指定回数並べる
+        get_year_month(start_date, end_date).repeat(repeat)
+    };
+    // オプション「--reverse-months」の場合は新しい月から並べる
+    if reverse_months {
+        year_months.reverse();
+    }
+
+    // オプション「--list-months」の処理
+    if list_months {
+        for (year, month) in year_months {
+            writeln!(out, "{year:04}-{month:02}")?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--summary」の処理
+    if summary {
+        writeln!(out, "{}", format_summary(start_date, end_date, &opts))?;
+        return Ok(());
+    }
+
+    // オプション「--business-days」の処理(「--events」の日付は休日として除く)
+    if business_days {
+        let days = count_business_days(&year_months, &opts.events);
+        writeln!(out, "{}", opts.format_number(days as f64, 0))?;
+        return Ok(());
+    }
+
+    // オプション「--weekends」の処理(複数の月の場合は合計も出力)
+    if weekends {
+        for &(year, month) in &year_months {
+            writeln!(out, "{}", format_weekend_count(year, month))?;
+        }
+        if year_months.len() > 1 {
+            let total: usize = year_months
+                .iter()
+                .map(|&(year, month)| count_weekend_days(year, month))
+                .sum();
+            writeln!(out, "Total: {}", opts.format_number(total as f64, 0))?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--agenda」の処理
+    if agenda {
+        for &(year, month) in &year_months {
+            for date in month_days(year, month) {
+                let highlighted = is_highlighted(date, &opts.highlights);
+                writeln!(out, "{}", format_agenda_line(date, highlighted))?;
+            }
+        }
+        return Ok(());
+    }
+
+    // オプション「--weeks-count」の処理
+    if weeks_count {
+        for (year, month) in year_months {
+            writeln!(out, "{}", week_rows(year, month, opts.first_day))?;
         }
+        return Ok(());
+    }
+
+    // オプション「--count-weekday」の処理
+    if let Some(weekday) = count_weekday {
+        for (year, month) in year_months {
+            writeln!(out, "{}", format_weekday_count(year, month, weekday))?;
+        }
+        return Ok(());
+    }
+
+    // オプション「--format=ndjson」の処理(1日1行で逐次出力)
+    if format == OutputFormat::Ndjson {
+        for &(year, month) in &year_months {
+            for date in month_days(year, month) {
+                writeln!(out, "{}", format_ndjson_line(date, today))?;
+            }
+        }
+        return Ok(());
+    }
+
+    // オプション「--format=tsv」の処理
+    if format == OutputFormat::Tsv {
+        for &(year, month) in &year_months {
+            for line in format_tsv_month(year, month, &opts) {
+                writeln!(out, "{line}")?;
+            }
+        }
+        return Ok(());
+    }
+
+    // オプション「--format=heatmap」の処理(対象年の全日付)
+    if format == OutputFormat::Heatmap {
+        write!(out, "{}", render_heatmap(year, today, &opts))?;
+        return Ok(());
+    }
 
-        // カレンダー生成
-        let calendar: Vec<_> = get_calendar(year_months, !all_same_year, today);
+    // オプション「--format=svg」の処理
+    if format == OutputFormat::Svg {
+        write!(
+            out,
+            "{}",
+            render_svg(&year_months, today, &opts, layout.months_per_row)
+        )?;
+        return Ok(());
+    }
 
-        // カレンダーを３ヶ月毎にコンソール出力
-        print_chunk_tree_month(calendar);
+    if year_months.len() == 1 {
+        // 単月のカレンダーをコンソール出力
+        let (year, month) = year_months[0];
+        let mut lines = format_month(year, month, true, today, &opts);
+        if compact {
+            // 末尾の空行を除去
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                lines.pop();
+            }
+        }
+        if let Some(style) = frame {
+            lines = frame_month(lines, style, opts.header_align);
+        }
+        let width = block_width(&lines);
+        for line in lines {
+            writeln!(out, "{line}{}", gap_after(&line, width, layout.trailing()))?;
+        }
     } else {
-        match month {
-            Some(month) => {
-                let lines = format_month(year, month, true, today);
-                println!("{}", lines.join("\n"));
+        // すべての年が同じか確認
+        let all_same_year = is_all_same_year(year_months.clone());
+        let full_year = all_same_year && year_months.len() == 12 && repeat == 1;
+        // 当日を含む月のみ枠線で囲む場合の位置(オプション「--frame-today-month」)
+        let today_index = year_months
+            .iter()
+            .position(|&year_month| year_month == (today.year(), today.month()))
+            .filter(|_| frame_today_month);
+        let year_width = match today_index {
+            Some(_) => year_header_width(
+                opts.line_width() + FRAME_EXTRA_WIDTH,
+                layout.months_per_row,
+                layout.gap().chars().count(),
+            ),
+            None => year_width,
+        };
+        // 年ヘッダを出力(オプション「--no-year」の場合は出力しない)
+        if !no_year {
+            if let Some(quarter) = quarter {
+                let label = format!("Q{quarter} {}", format_year_label(year, None, &opts));
+                writeln!(out, "{}", format_year_header(&label, year_width))?;
+            } else if all_same_year {
+                let mut label = format_year_label(year_months[0].0, None, &opts);
+                // 1年分の場合は閏年を表示(オプション「--show-leap」)
+                if show_leap && full_year && is_leap_year(year_months[0].0) {
+                    label.push_str(" (leap year)");
+                }
+                writeln!(out, "{}", format_year_header(&label, year_width))?;
+            }
+        }
+
+        // カレンダーを1行の月数毎に生成して出力(全期間の月を保持せず、長い期間でもメモリを抑える)
+        let last = year_months.len() - 1;
+        stream_chunk_tree_month(out, year_months.len(), &layout, |i| {
+            let (year, month) = year_months[i];
+            let mut lines = format_month(year, month, !all_same_year, today, &opts);
+            // 「-3」の前後の月を薄く表示(オプション「--dim-adjacent」)
+            if dim_adjacent && three_flg && (i == 0 || i == last) {
+                lines = dim_month(lines);
+            }
+            // 当日を含む月のみ囲む場合、他の月は空白で囲んで大きさを揃える
+            match (today_index, frame) {
+                (Some(index), _) if i == index => {
+                    frame_month(lines, FrameStyle::Unicode, opts.header_align)
+                }
+                (Some(_), _) => pad_month(lines),
+                (None, Some(style)) => frame_month(lines, style, opts.header_align),
+                (None, None) => lines,
             }
-            None => {
-                println!("{year:>32}");
+        })?;
 
-                // 対象とする期間を決定(対象年1年間)
-                let start_date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
-                let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
-                let year_months = get_year_month(start_date, end_date);
+        // 当日の年の進捗を出力(オプション「--year-progress」、表示した年が当日の年の場合のみ)
+        if year_progress && full_year && year_months[0].0 == today.year() {
+            writeln!(out, "{}", format_year_progress(today, &opts))?;
+        }
+    }
 
-                // カレンダー生成
-                let calendar: Vec<_> = get_calendar(year_months, false, today);
+    Ok(())
+}
 
-                // カレンダーを３ヶ月毎にコンソール出力
-                print_chunk_tree_month(calendar);
+/// 設定ファイルの既定値を重ねたコマンドライン引数を取得
+/// 設定ファイルの既定値をコマンドライン引数の前に置き、コマンドラインで指定したオプションを優先する。
+/// 設定ファイルが読めない・解析できない場合は警告のみ出力して、コマンドライン引数だけを使う。
+/// コマンドライン引数と両立しない既定値(例: `quiet = true`と`--output`)は、その既定値だけを警告して除く。
+fn args_with_config() -> Result<Args> {
+    let matches = Args::command().get_matches();
+    let Some(path) = config_path() else {
+        return Ok(Args::from_arg_matches(&matches)?);
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            // 既定の場所に設定ファイルがないのは正常
+            if e.kind() != io::ErrorKind::NotFound || env::var_os("CALR_CONFIG").is_some() {
+                eprintln!("{}: {e}", path.display());
             }
+            return Ok(Args::from_arg_matches(&matches)?);
         }
+    };
+    let mut config = parse_config(&content);
+    for (line_number, line) in &config.malformed {
+        eprintln!(
+            r#"{}:{line_number}: Invalid config "{line}""#,
+            path.display()
+        );
     }
+    let command = Args::command();
+    config.entries.retain(|(key, _)| {
+        let Some(arg) = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(key.as_str()))
+        else {
+            eprintln!(r#"{}: Invalid config key "{key}""#, path.display());
+            return false;
+        };
+        matches.value_source(arg.get_id().as_str()) != Some(ValueSource::CommandLine)
+    });
 
-    Ok(String::from("Success"))
+    // 既定値をプログラム名の直後に置いて解析する
+    let argv: Vec<_> = env::args_os().collect();
+    let (program, rest) = argv.split_at(argv.len().min(1));
+    let merge = |entries: &[(String, ConfigValue)]| {
+        let defaults = ConfigFile {
+            entries: entries.to_vec(),
+            ..Default::default()
+        }
+        .to_args();
+        command.clone().try_get_matches_from(
+            program
+                .iter()
+                .cloned()
+                .chain(defaults.into_iter().map(Into::into))
+                .chain(rest.iter().cloned()),
+        )
+    };
+    if merge(&config.entries).is_err() {
+        config
+            .entries
+            .retain(|entry| match merge(std::slice::from_ref(entry)) {
+                Ok(_) => true,
+                Err(e) => {
+                    let message = e.to_string();
+                    eprintln!(
+                        r#"{}: Ignored config key "{}": {}"#,
+                        path.display(),
+                        entry.0,
+                        message.lines().next().unwrap_or_default()
+                    );
+                    false
+                }
+            });
+    }
+    match merge(&config.entries) {
+        Ok(merged) => Ok(Args::from_arg_matches(&merged)?),
+        Err(e) => {
+            let message = e.to_string();
+            eprintln!(
+                "{}: {}",
+                path.display(),
+                message.lines().next().unwrap_or_default()
+            );
+            Ok(Args::from_arg_matches(&matches)?)
+        }
+    }
 }
 
 /// コマンドライン引数を解析
-fn parse_args() -> Result<(Option<i32>, Option<u32>, NaiveDate, bool), Error> {
-    let args = Args::parse();
-    let today = Local::now().date_naive();
+fn parse_args() -> Result<Config, Error> {
+    let args = args_with_config()?;
+    // 当日日付(環境変数「CALR_TODAY」で固定可能)
+    let today = match (env::var("CALR_TODAY"), args.timezone) {
+        (Ok(date), _) => parse_date(date)?,
+        (_, Some(ref name)) => Utc::now()
+            .with_timezone(&parse_timezone(name)?)
+            .date_naive(),
+        (_, None) => Local::now().date_naive(),
+    };
+    let locale = match args.locale {
+        Some(ref locale) => parse_locale(locale)?,
+        None => Locale::default(),
+    };
     let mut year = args.year;
-    let mut month = args.month.map(parse_month).transpose()?;
+    // 複数の月は重複を除き暦順に並べる
+    let mut months = args
+        .month
+        .into_iter()
+        .map(|month| parse_localized_month(month, &locale, args.strict))
+        .collect::<Result<Vec<_>>>()?;
+    months.sort_unstable();
+    months.dedup();
+    // 「月 年」の順の2つの位置引数(GNU calと同様、先頭が1〜12の場合のみ)
+    let mut more_years = args.more_years;
+    if months.is_empty() && more_years.len() == 1 && year.is_some_and(|year| year <= 12) {
+        months.extend(year.map(|month| month as u32));
+        year = more_years.pop();
+    }
+    if months.len() > 1 && (args.three || args.summary || !args.diff.is_empty()) {
+        bail!("multiple months cannot be combined with -3, --summary or --diff");
+    }
+    let mut month = months.first().copied();
 
-    if args.show_current_year {
-        year = Some(today.year());
+    // 当日の年月(ペルシア暦の場合はペルシア暦の年月)
+    let (today_year, today_month) = match args.calendar {
+        CalendarKind::Gregorian => (today.year(), today.month()),
+        CalendarKind::Persian => {
+            let (year, month, _) = to_jalali(today);
+            (year, month)
+        }
+    };
+    let around = args.around.map(parse_date).transpose()?;
+    if let Some(date) = around {
+        year = Some(date.year());
+        month = Some(date.month());
+    } else if args.show_current_year {
+        year = Some(today_year);
         month = None;
     } else if month.is_none() && year.is_none() {
-        year = Some(today.year());
-        month = Some(today.month());
+        year = Some(today_year);
+        month = Some(today_month);
     }
-    Ok((year, month, today, args.three))
-}
 
-/// カレンダーを３ヶ月毎にコンソール出力
-fn print_chunk_tree_month(calendar: Vec<Vec<String>>) {
-    for (i, chunk) in calendar.chunks(3).enumerate() {
-        if let [m1, m2, m3] = chunk {
-            for lines in izip!(m1, m2, m3) {
-                println!("{}{}{}", lines.0, lines.1, lines.2);
+    if args.from_day.is_some() && (month.is_none() || months.len() > 1) {
+        bail!("--from-day requires a single month");
+    }
+    if let (Some(day), Some(month)) = (args.from_day, month) {
+        let year = year.unwrap_or(today_year);
+        let days = days_in_month(year, month);
+        if day > days {
+            bail!(r#"--from-day "{day}" exceeds the {days} days of {year}-{month:02}"#);
+        }
+    }
+    // 「--rolling-year」の場合は対象月を年度開始月として12ヶ月を表示する
+    let mut fiscal_start = args.fiscal_start.unwrap_or(1);
+    if args.rolling_year {
+        if months.len() > 1 {
+            bail!("--rolling-year requires a single month");
+        }
+        fiscal_start = month.take().unwrap_or(today_month);
+    }
+
+    if args.day_separator.is_empty() {
+        bail!(r#"Invalid day separator """#);
+    }
+
+    if args.repeat.is_some() && (month.is_none() || months.len() > 1) {
+        bail!("--repeat requires a single month");
+    }
+    if args.dual.is_some() && (month.is_none() || months.len() > 1) {
+        bail!("--dual requires a single month");
+    }
+    if args.split_output.is_some() && args.format == OutputFormat::Heatmap {
+        bail!("--split-output does not support --format heatmap");
+    }
+    // 「--interactive」は「tui」フィーチャーでビルドした場合のみ使える
+    if args.interactive && !cfg!(all(feature = "tui", unix)) {
+        bail!("--interactive requires calr to be built with the tui feature (Unix only)");
+    }
+
+    let recur = args
+        .recur
+        .into_iter()
+        .map(|weekday| parse_localized_weekday(weekday, &locale))
+        .collect::<Result<_>>()?;
+
+    let count_weekday = args
+        .count_weekday
+        .map(|weekday| parse_localized_weekday(weekday, &locale))
+        .transpose()?;
+
+    let span = match (args.from, args.to) {
+        (Some(from), Some(to)) => {
+            let (from, to) = (parse_year_month(from)?, parse_year_month(to)?);
+            if to < from {
+                bail!(
+                    r#"--to "{}-{:02}" is earlier than --from "{}-{:02}""#,
+                    to.0,
+                    to.1,
+                    from.0,
+                    from.1
+                );
+            }
+            Some((from, to))
+        }
+        _ => None,
+    };
+
+    let diff = match args.diff[..] {
+        [] => None,
+        [year_a, year_b] => Some((year_a, year_b)),
+        _ => bail!("--diff requires exactly two years"),
+    };
+
+    let frame = match (args.ascii_box, args.ascii) {
+        (false, _) => None,
+        (true, false) => Some(FrameStyle::Unicode),
+        (true, true) => Some(FrameStyle::Ascii),
+    };
+
+    let first_day = match args.first_day.as_deref() {
+        None if args.iso => Weekday::Mon,
+        None if args.saturday => Weekday::Sat,
+        None => Weekday::Sun,
+        Some("auto") => locale.first_day(),
+        Some(weekday) => parse_localized_weekday(weekday.to_string(), &locale)?,
+    };
+
+    let mut highlights: HashSet<_> = args
+        .highlight
+        .into_iter()
+        .map(parse_date)
+        .collect::<Result<_>>()?;
+    for range in args.highlight_range {
+        highlights.extend(parse_date_range(range)?);
+    }
+    highlights.extend(around);
+
+    // 解析できない行は警告のみ出力してカレンダーの出力は続ける
+    let events = match args.events {
+        Some(path) => {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => bail!("{path}: {e}"),
+            };
+            let events = parse_events(&content);
+            for (line_number, line) in &events.malformed {
+                eprintln!(r#"{path}:{line_number}: Invalid event "{line}""#);
             }
-            if i < 3 {
-                println!();
+            events.dates
+        }
+        None => HashSet::new(),
+    };
+
+    // 端末に出力するか否か(「--color=auto」の装飾とハイパーリンク、1行の月数の判定に使用)
+    // 「--output」「--split-output」「--checksum」の場合は端末に関わらず装飾しない
+    let terminal = args.output.is_none()
+        && args.split_output.is_none()
+        && !args.checksum
+        && io::stdout().is_terminal();
+    let decorate = !args.checksum && use_color(args.color, terminal);
+    let theme = match (decorate, args.theme) {
+        (false, _) => Theme::plain(),
+        (true, ThemeName::Default) => Theme::default(),
+        (true, ThemeName::Solarized) => Theme::solarized(),
+        (true, ThemeName::Mono) => Theme::mono(),
+    };
+    // 役割毎の文字色(「--style」)は装飾しない場合も検証だけ行う
+    let theme = match args.style {
+        Some(ref spec) => {
+            // 端末が24ビットカラーに対応しない場合(環境変数「COLORTERM」で判定)は基本の色に丸める
+            let truecolor = args.truecolor
+                && (!terminal
+                    || env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit"));
+            let styled = theme.with_style_spec(spec, truecolor)?;
+            if decorate {
+                styled
+            } else {
+                theme
+            }
+        }
+        None => theme,
+    };
+    let opts = FormatOptions {
+        wide_weekdays: args.wide_weekdays,
+        highlights,
+        events,
+        locale,
+        first_day,
+        japanese_era: args.era == Some(EraKind::Japanese),
+        week_column: if let Some(epoch) = args.week_epoch {
+            Some(WeekColumn::WeeksSince {
+                epoch: parse_date(epoch)?,
+                digits: 2,
+            })
+        } else if args.iso || args.week_rule.is_some() {
+            Some(WeekColumn::WeekNumber(match args.week_rule {
+                None | Some(WeekRuleName::Iso) => WeekRule::Iso,
+                Some(WeekRuleName::Jan1) => WeekRule::Jan1,
+                Some(WeekRuleName::FirstFull) => WeekRule::FirstFull,
+            }))
+        } else if args.week_of_month {
+            Some(WeekColumn::WeekOfMonth)
+        } else {
+            args.debug_columns.then_some(WeekColumn::DebugOffset)
+        },
+        theme: match args.today_style {
+            _ if args.no_highlight_today => theme.with_today_style(Style::new()),
+            TodayStyle::Reverse => theme,
+            TodayStyle::Bracket => theme.with_today_style(Style::new()),
+            TodayStyle::Underline if !decorate => theme,
+            TodayStyle::Underline => theme.with_today_style(Style::new().underline()),
+        },
+        today_brackets: args.today_style == TodayStyle::Bracket && !args.no_highlight_today,
+        title_below: args.invert,
+        from_day: args.from_day,
+        roman_months: args.roman,
+        header_gap: args.header_gap,
+        min_width: args.min_width.unwrap_or_default(),
+        mark_boundaries: args.mark_boundaries,
+        highlight_week: args.highlight_week.then_some(today),
+        locale_number: args.locale_number,
+        recur,
+        no_weekday_header: args.no_weekday_header,
+        header_align: match args.header_align {
+            HeaderAlignName::Left => HeaderAlign::Left,
+            HeaderAlignName::Center => HeaderAlign::Center,
+            HeaderAlignName::Right => HeaderAlign::Right,
+        },
+        arabic_digits: args.digits == Digits::Arabic,
+        collapse_weekends: args.collapse_weekends,
+        zero_pad_year: args.zero_pad_year,
+        astronomical_year: args.astronomical_year,
+        moon: args.moon,
+        day_separator: args.day_separator,
+        continuous_days: None,
+        // ハイパーリンクは端末に出力する場合のみ有効
+        link_format: args.link_format.filter(|_| terminal),
+    };
+
+    let gap_width = match args.separator {
+        Some(ref separator) => separator.chars().count(),
+        None => DEFAULT_SEPARATOR.len(),
+    };
+    let layout = Layout {
+        months_per_row: match args.months_per_row {
+            Some(n) => n as usize,
+            // ファイル・「--checksum」への出力は端末幅に左右されないようにする
+            None => auto_months_per_row(
+                env::var("COLUMNS")
+                    .ok()
+                    .filter(|_| args.output.is_none() && args.split_output.is_none())
+                    .filter(|_| !args.checksum)
+                    .as_deref(),
+                terminal,
+                month_width(frame, &opts)
+                    + gap_width
+                    + usize::from(args.frame_today_month) * FRAME_EXTRA_WIDTH,
+                args.indent,
+            ),
+        },
+        rtl: args.rtl,
+        separator: args.separator,
+        grid_lines: args.grid_lines,
+    };
+
+    // 複数の年(位置引数の追加の年、または「--years」の範囲)
+    let years = match args.years {
+        Some(range) => {
+            let years = parse_year_range(range)?;
+            year = years.first().copied();
+            month = None;
+            years
+        }
+        None if !more_years.is_empty() => {
+            month = None;
+            year.into_iter().chain(more_years).collect()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Config {
+        year,
+        years,
+        month,
+        months,
+        today,
+        three: args.three,
+        quarter: args.quarter,
+        fiscal_start,
+        frame,
+        frame_today_month: args.frame_today_month,
+        // 装飾しない場合は薄く表示しない
+        dim_adjacent: args.dim_adjacent && decorate,
+        count_weekday,
+        span,
+        list_months: args.list_months,
+        summary: args.summary,
+        business_days: args.business_days,
+        weekends: args.weekends,
+        compact: args.compact,
+        no_year: args.no_year,
+        show_leap: args.show_leap,
+        year_progress: args.year_progress,
+        iso_week: args.iso_week.map(parse_date).transpose()?,
+        iso_year: args.iso_year.map(parse_date).transpose()?,
+        print_today: args.print_today,
+        between: match args.between[..] {
+            [ref from, ref to] => Some((parse_date(from.clone())?, parse_date(to.clone())?)),
+            _ => None,
+        },
+        months_between: match args.months_between[..] {
+            [ref from, ref to] => Some((
+                parse_year_month(from.clone())?,
+                parse_year_month(to.clone())?,
+            )),
+            _ => None,
+        },
+        week: args.week,
+        peek: args.peek,
+        reverse_months: args.reverse_months,
+        continuous_days: args.continuous_days,
+        repeat: args.repeat.unwrap_or(1) as usize,
+        layout,
+        agenda: args.agenda,
+        weeks_count: args.weeks_count,
+        diff,
+        dual: args.dual,
+        names: matches!(args.command, Some(Commands::Names)),
+        parsed_date: match args.command {
+            Some(Commands::Parse { ref date }) => Some(parse_loose_date(date.clone())?),
+            _ => None,
+        },
+        weekday_of: match args.command {
+            Some(Commands::Weekday { date }) => Some(parse_date(date)?),
+            _ => None,
+        },
+        format: args.format,
+        output: args.output,
+        split_output: args.split_output,
+        interactive: args.interactive,
+        checksum: args.checksum,
+        quiet: args.quiet,
+        validate_only: args.validate_only,
+        indent: args.indent,
+        calendar: args.calendar,
+        opts,
+    })
+}
+
+/// 引数・タイムゾーンの解析
+/// 受け取ったIANAタイムゾーン名を解析してTz型に変換して返す。
+///
+/// * `name` - IANAタイムゾーン名
+fn parse_timezone(name: &str) -> Result<Tz> {
+    match name.parse() {
+        Ok(tz) => Ok(tz),
+        _ => bail!(r#"Invalid timezone "{name}""#),
+    }
+}
+
+/// 月カレンダー1つ分の出力幅を取得
+///
+/// * `frame` - 枠線の種類
+/// * `opts`  - フォーマット設定
+fn month_width(frame: Option<FrameStyle>, opts: &FormatOptions) -> usize {
+    match frame {
+        Some(_) => opts.line_width() + FRAME_EXTRA_WIDTH,
+        None => opts.line_width(),
+    }
+}
+
+/// 1行に並べる月数を端末幅から決定
+/// 端末に出力していて`COLUMNS`が設定されている場合は収まるだけ並べ、それ以外は３ヶ月とする。
+/// 端末以外への出力でも`COLUMNS`が３ヶ月分より狭い場合は、行が折り返さないよう収まる月数(最低1ヶ月)に減らす。
+/// 行頭の字下げ(`--indent`)の分は端末幅から除く。
+///
+/// * `columns`     - 環境変数`COLUMNS`の値
+/// * `is_tty`      - 出力先が端末か否か
+/// * `month_width` - 月カレンダー1つ分の出力幅(月同士の区切りを含む)
+/// * `indent`      - 行頭の字下げの桁数
+fn auto_months_per_row(
+    columns: Option<&str>,
+    is_tty: bool,
+    month_width: usize,
+    indent: usize,
+) -> usize {
+    let columns = columns.and_then(|columns| columns.trim().parse::<usize>().ok());
+    match columns.map(|columns| columns.saturating_sub(indent)) {
+        Some(columns) if is_tty => (columns / month_width).max(1),
+        Some(columns) => (columns / month_width).clamp(1, 3),
+        None => 3,
+    }
+}
+
+/// 装飾を行うか否かを決定
+/// 「--color=auto」の場合は端末に出力している場合のみ装飾する。
+///
+/// * `choice` - 装飾の指定
+/// * `is_tty` - 出力先が端末か否か
+fn use_color(choice: ColorChoice, is_tty: bool) -> bool {
+    match choice {
+        ColorChoice::Auto => is_tty,
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    }
+}
+
+/// 年ヘッダの右寄せ幅を取得
+/// 1行分の幅の中央付近に年が表示されるように算出する。
+///
+/// * `month_width`    - 月カレンダー1つ分の出力幅
+/// * `months_per_row` - 1行に並べる月数
+/// * `gap_width`      - 月同士の区切りの幅
+fn year_header_width(month_width: usize, months_per_row: usize, gap_width: usize) -> usize {
+    (month_width * months_per_row + gap_width * (months_per_row - 1)) / 2
+}
+
+/// 年ヘッダをフォーマットする
+/// 4桁の年を基準とした右寄せ幅に、ラベルが長い分の半分を加えて中央付近に揃える。
+///
+/// * `label`      - 年ヘッダのラベル
+/// * `year_width` - 年ヘッダの右寄せ幅
+fn format_year_header(label: &str, year_width: usize) -> String {
+    let label_width = label.width();
+    let width = year_width + label_width.saturating_sub(4) / 2;
+    format!("{}{label}", " ".repeat(width.saturating_sub(label_width)))
+}
+
+/// 月カレンダーの幅(各行の表示幅のうち最小のもの)
+/// 当日の閉じ括弧(`--today-style=bracket`)が右端からはみ出す行は幅に含めない。
+///
+/// * `lines` - 月カレンダーの行
+fn block_width(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .map(|line| visible_width(line))
+        .min()
+        .unwrap_or_default()
+}
+
+/// 月カレンダーの行の後ろに付与する区切りを取得
+/// 当日の閉じ括弧が月カレンダーの幅からはみ出す行は、はみ出した分だけ区切りの先頭を削って桁を揃える。
+///
+/// * `line`  - 月カレンダーの行
+/// * `width` - 月カレンダーの幅
+/// * `gap`   - 行の後ろに付与する区切り
+fn gap_after(line: &str, width: usize, gap: &str) -> String {
+    let overflow = visible_width(line).saturating_sub(width);
+    gap.chars().skip(overflow).collect()
+}
+
+/// 月カレンダーの各行を薄く表示する
+/// 日付セルの装飾の終了で薄い表示が途切れないよう、装飾の終了の後に薄い表示を再開する。
+///
+/// * `lines` - 月カレンダーの行
+fn dim_month(lines: Vec<String>) -> Vec<String> {
+    let dim = Style::new().dimmed();
+    let reset = "\u{1b}[0m";
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = line.replace(reset, &format!("{reset}{}", dim.prefix()));
+            dim.paint(line).to_string()
+        })
+        .collect()
+}
+
+/// カレンダーの各月に枠線を付与
+///
+/// * `calendar` - 月毎の行のVec
+/// * `frame`    - 枠線の種類
+/// * `align`    - 月ヘッダの寄せ方
+fn apply_frame(
+    calendar: Vec<Vec<String>>,
+    frame: Option<FrameStyle>,
+    align: HeaderAlign,
+) -> Vec<Vec<String>> {
+    match frame {
+        Some(style) => calendar
+            .into_iter()
+            .map(|lines| frame_month(lines, style, align))
+            .collect(),
+        None => calendar,
+    }
+}
+
+/// カレンダーをレイアウトの月数毎に出力
+/// 月数が1行の月数の倍数でない場合、最終行は残りの月だけを出力する。
+///
+/// * `out`      - 出力先
+/// * `calendar` - 月毎の行のVec
+/// * `layout`   - レイアウト設定
+fn print_chunk_tree_month(
+    out: &mut dyn Write,
+    mut calendar: Vec<Vec<String>>,
+    layout: &Layout,
+) -> Result<()> {
+    stream_chunk_tree_month(out, calendar.len(), layout, |i| {
+        std::mem::take(&mut calendar[i])
+    })
+}
+
+/// カレンダーをレイアウトの月数毎に生成しながら出力
+/// 1行分の月だけを生成して出力し、出力後に破棄する。出力は`print_chunk_tree_month`と同じ。
+///
+/// * `out`         - 出力先
+/// * `months`      - 月数
+/// * `layout`      - レイアウト設定
+/// * `month_lines` - 添字の月の行を生成する関数
+fn stream_chunk_tree_month(
+    out: &mut dyn Write,
+    months: usize,
+    layout: &Layout,
+    mut month_lines: impl FnMut(usize) -> Vec<String>,
+) -> Result<()> {
+    let rows = months.div_ceil(layout.months_per_row);
+    for i in 0..rows {
+        let start = i * layout.months_per_row;
+        let end = months.min(start + layout.months_per_row);
+        let mut chunk: Vec<_> = (start..end).map(&mut month_lines).collect();
+        if layout.rtl {
+            chunk.reverse();
+        }
+        let widths: Vec<_> = chunk.iter().map(|month| block_width(month)).collect();
+        let width = widths.iter().sum::<usize>() + visible_width(layout.gap()) * (chunk.len() - 1);
+        for line in 0..chunk[0].len() {
+            let mut joined = String::new();
+            for (i, month) in chunk.iter().enumerate() {
+                if i > 0 {
+                    joined.push_str(&gap_after(&chunk[i - 1][line], widths[i - 1], layout.gap()));
+                }
+                joined.push_str(&month[line]);
             }
+            let last = chunk.len() - 1;
+            let trailing = gap_after(&chunk[last][line], widths[last], layout.trailing());
+            writeln!(out, "{joined}{trailing}")?;
         }
+        // 行間の空行(1行のみの場合は末尾に出力)、罫線を引く場合は行間を罫線にする
+        if layout.grid_lines && i + 1 < rows {
+            writeln!(out, "{}", "-".repeat(width))?;
+        } else if rows == 1 || i + 1 < rows {
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{
+        auto_months_per_row, format_year_header, print_chunk_tree_month, stream_chunk_tree_month,
+        use_color, year_header_width, ColorChoice, IndentWriter, Layout,
+    };
+    use crate::utils::date_util::{format_month, get_calendar, get_year_month, FormatOptions};
+    use chrono::NaiveDate;
+    use std::io::Write;
+
+    #[test]
+    fn test_print_chunk_tree_month() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let calendar = get_calendar(
+            vec![(2020, 4), (2020, 5), (2020, 6), (2020, 7)],
+            false,
+            today,
+            &FormatOptions::default(),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let layout = Layout {
+            months_per_row: 3,
+            rtl: false,
+            separator: None,
+            grid_lines: false,
+        };
+        print_chunk_tree_month(&mut out, calendar, &layout).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines.len(), 18);
+        assert_eq!(
+            lines[0],
+            "       April                  May                   June          "
+        );
+        assert_eq!(
+            lines[2],
+            "          1  2  3  4                  1  2      1  2  3  4  5  6  "
+        );
+        assert_eq!(lines[8], "");
+        assert_eq!(lines[9], "        July          ");
+        assert_eq!(lines[17], "");
+    }
+
+    #[test]
+    fn test_stream_chunk_tree_month() {
+        // 10年分(120ヶ月)を一括で生成した場合と、1行ずつ生成した場合の出力が一致する
+        let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let year_months = get_year_month(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2029, 12, 31).unwrap(),
+        );
+        assert_eq!(year_months.len(), 120);
+        let opts = FormatOptions::default();
+        let layout = Layout {
+            months_per_row: 3,
+            rtl: false,
+            separator: None,
+            grid_lines: false,
+        };
+
+        let mut buffered: Vec<u8> = Vec::new();
+        let calendar = get_calendar(year_months.clone(), true, today, &opts);
+        print_chunk_tree_month(&mut buffered, calendar, &layout).unwrap();
+
+        let mut streamed: Vec<u8> = Vec::new();
+        let mut generated = 0;
+        stream_chunk_tree_month(&mut streamed, year_months.len(), &layout, |i| {
+            generated += 1;
+            let (year, month) = year_months[i];
+            format_month(year, month, true, today, &opts)
+        })
+        .unwrap();
+
+        assert_eq!(generated, 120);
+        assert_eq!(streamed, buffered);
+    }
+
+    #[test]
+    fn test_print_chunk_tree_month_rtl() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let calendar = get_calendar(
+            vec![(2020, 4), (2020, 5), (2020, 6)],
+            false,
+            today,
+            &FormatOptions::default(),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let layout = Layout {
+            months_per_row: 3,
+            rtl: true,
+            separator: None,
+            grid_lines: false,
+        };
+        print_chunk_tree_month(&mut out, calendar, &layout).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(
+            lines[0],
+            "        June                  May                  April          "
+        );
+        assert_eq!(
+            lines[2],
+            "    1  2  3  4  5  6                  1  2            1  2  3  4  "
+        );
+    }
+
+    #[test]
+    fn test_print_chunk_tree_month_months_per_row() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let calendar = get_calendar(
+            vec![(2020, 4), (2020, 5), (2020, 6), (2020, 7), (2020, 8)],
+            false,
+            today,
+            &FormatOptions::default(),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let layout = Layout {
+            months_per_row: 4,
+            rtl: false,
+            separator: None,
+            grid_lines: false,
+        };
+        print_chunk_tree_month(&mut out, calendar, &layout).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines[0].len(), 22 * 4);
+        assert_eq!(lines[9].trim(), "August");
+    }
+
+    #[test]
+    fn test_print_chunk_tree_month_separator() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let calendar = get_calendar(
+            vec![(2020, 4), (2020, 5)],
+            false,
+            today,
+            &FormatOptions::default(),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let layout = Layout {
+            months_per_row: 3,
+            rtl: false,
+            separator: Some(String::from(" │ ")),
+            grid_lines: false,
+        };
+        print_chunk_tree_month(&mut out, calendar, &layout).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa │ Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[7], "                     │ 31                  ");
+    }
+
+    #[test]
+    fn test_print_chunk_tree_month_grid_lines() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let calendar = get_calendar(
+            vec![(2020, 4), (2020, 5), (2020, 6), (2020, 7)],
+            false,
+            today,
+            &FormatOptions::default(),
+        );
+        let mut out: Vec<u8> = Vec::new();
+        let layout = Layout {
+            months_per_row: 3,
+            rtl: false,
+            separator: None,
+            grid_lines: true,
+        };
+        print_chunk_tree_month(&mut out, calendar, &layout).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.split('\n').collect();
+        // 罫線は月3つと区切り2つ分の幅
+        assert_eq!(lines[8], "-".repeat(20 * 3 + 2 * 2));
+        assert_eq!(lines[9].trim(), "July");
+        // 最後の行の後には罫線を引かない
+        assert_eq!(lines.iter().filter(|line| line.starts_with('-')).count(), 1);
+    }
+
+    #[test]
+    fn test_auto_months_per_row() {
+        assert_eq!(auto_months_per_row(Some("200"), true, 22, 0), 9);
+        assert_eq!(auto_months_per_row(Some("80"), true, 22, 0), 3);
+        assert_eq!(auto_months_per_row(Some("30"), true, 22, 0), 1);
+        assert_eq!(auto_months_per_row(Some("10"), true, 22, 0), 1);
+        assert_eq!(auto_months_per_row(Some("200"), false, 22, 0), 3);
+        assert_eq!(auto_months_per_row(Some("50"), false, 22, 0), 2);
+        assert_eq!(auto_months_per_row(Some("30"), false, 22, 0), 1);
+        assert_eq!(auto_months_per_row(None, true, 22, 0), 3);
+        assert_eq!(auto_months_per_row(Some("foo"), true, 22, 0), 3);
+        // 字下げの分だけ狭くなる
+        assert_eq!(auto_months_per_row(Some("66"), true, 22, 8), 2);
+        assert_eq!(auto_months_per_row(Some("66"), false, 22, 8), 2);
+        assert_eq!(auto_months_per_row(Some("20"), true, 22, 30), 1);
+    }
+
+    #[test]
+    fn test_use_color() {
+        assert!(use_color(ColorChoice::Auto, true));
+        assert!(!use_color(ColorChoice::Auto, false));
+        assert!(use_color(ColorChoice::Always, false));
+        assert!(!use_color(ColorChoice::Never, true));
+    }
+
+    #[test]
+    fn test_indent_writer() {
+        let mut buf = Vec::new();
+        let mut out = IndentWriter::new(&mut buf, 4);
+        // 行の途中で分割して書き込んでも行頭にのみ付与する
+        write!(out, "Su Mo").unwrap();
+        writeln!(out, " Tu").unwrap();
+        write!(out, "\n 1  2\n").unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "    Su Mo Tu\n\n     1  2\n"
+        );
+    }
+
+    #[test]
+    fn test_format_year_header() {
+        assert_eq!(format_year_header("2020", 32), format!("{:>32}", "2020"));
+        assert_eq!(format_year_header("999", 32), format!("{:>32}", "999"));
+        assert_eq!(
+            format_year_header("Q1 2024", 32),
+            format!("{:>33}", "Q1 2024")
+        );
+        assert_eq!(
+            format_year_header("令和6年", 32),
+            format!("{:>30}", "令和6年")
+        );
+    }
+
+    #[test]
+    fn test_year_header_width() {
+        assert_eq!(year_header_width(20, 3, 2), 32);
+        assert_eq!(year_header_width(22, 3, 2), 35);
+        assert_eq!(year_header_width(20, 4, 2), 43);
+        assert_eq!(year_header_width(20, 3, 3), 33);
     }
 }
@@ -1,5 +1,10 @@
+use std::io::{BufWriter, Write};
+
 fn main() {
-    if let Err(e) = calr::run() {
+    // 行毎の書き込みを避けるため、バッファリングして最後に一度だけフラッシュする
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let result = calr::run(&mut out).and_then(|_| Ok(out.flush()?));
+    if let Err(e) = result {
         eprintln!("{e}");
         std::process::exit(1);
     }
@@ -1 +1,15 @@
+pub mod browse_util;
+pub mod config_util;
 pub mod date_util;
+pub mod era_util;
+pub mod event_util;
+pub mod frame_util;
+pub mod hash_util;
+pub mod heatmap_util;
+pub mod locale_util;
+pub mod moon_util;
+pub mod persian_util;
+pub mod svg_util;
+pub mod theme_util;
+#[cfg(all(feature = "tui", unix))]
+pub mod tui_util;
@@ -0,0 +1,126 @@
+use crate::utils::date_util::first_of_month_offset;
+use chrono::{Datelike, NaiveDate};
+
+/// 月の切り替え画面(`--interactive`)で扱うキー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// 前の月(←・↑)
+    Previous,
+    /// 次の月(→・↓)
+    Next,
+    /// 当日の月(`t`)
+    Today,
+    /// 終了(`q`・Ctrl-C)
+    Quit,
+    /// それ以外のキー
+    Other,
+}
+
+/// 端末から読み込んだバイト列をキーに変換
+/// 矢印キーは`ESC [ A`〜`ESC [ D`(または`ESC O A`〜`ESC O D`)として送られる。
+///
+/// * `bytes` - 1回の読み込みで得たバイト列
+pub fn parse_key(bytes: &[u8]) -> Key {
+    match bytes {
+        [0x1b, b'[' | b'O', b'A' | b'D', ..] => Key::Previous,
+        [0x1b, b'[' | b'O', b'B' | b'C', ..] => Key::Next,
+        [b't' | b'T', ..] => Key::Today,
+        [b'q' | b'Q', ..] | [0x03, ..] => Key::Quit,
+        _ => Key::Other,
+    }
+}
+
+/// 月の切り替え画面の状態
+/// 表示中の年月を持ち、キー入力に応じて1ヶ月ずつ前後に移動する(1年1月〜9999年12月の範囲)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthBrowser {
+    /// 表示中の年
+    pub year: i32,
+    /// 表示中の月
+    pub month: u32,
+    /// 当日日付(`t`で戻る月)
+    today: NaiveDate,
+}
+
+impl MonthBrowser {
+    /// 最初に表示する年月と当日日付から生成
+    ///
+    /// * `year`  - 対象年
+    /// * `month` - 対象月
+    /// * `today` - 当日日付
+    pub fn new(year: i32, month: u32, today: NaiveDate) -> Self {
+        MonthBrowser { year, month, today }
+    }
+
+    /// キー入力を処理して、画面を続けるか否かを返す
+    ///
+    /// * `key` - 入力されたキー
+    pub fn handle(&mut self, key: Key) -> bool {
+        match key {
+            Key::Previous => self.shift(-1),
+            Key::Next => self.shift(1),
+            Key::Today => {
+                self.year = self.today.year();
+                self.month = self.today.month();
+            }
+            Key::Quit => return false,
+            Key::Other => {}
+        }
+        true
+    }
+
+    /// 表示中の年月をdeltaヶ月ずらす(範囲外になる場合は移動しない)
+    ///
+    /// * `delta` - ずらす月数
+    fn shift(&mut self, delta: i32) {
+        let (year, month) = first_of_month_offset(self.year, self.month, delta);
+        if (1..=9999).contains(&year) {
+            self.year = year;
+            self.month = month;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{parse_key, Key, MonthBrowser};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key(b"\x1b[D"), Key::Previous);
+        assert_eq!(parse_key(b"\x1b[A"), Key::Previous);
+        assert_eq!(parse_key(b"\x1bOC"), Key::Next);
+        assert_eq!(parse_key(b"\x1b[B"), Key::Next);
+        assert_eq!(parse_key(b"t"), Key::Today);
+        assert_eq!(parse_key(b"q"), Key::Quit);
+        assert_eq!(parse_key(&[0x03]), Key::Quit);
+        assert_eq!(parse_key(b"\x1b"), Key::Other);
+        assert_eq!(parse_key(b"x"), Key::Other);
+    }
+
+    #[test]
+    fn test_month_browser() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 13).unwrap();
+        let mut browser = MonthBrowser::new(2024, 12, today);
+        assert!(browser.handle(Key::Next));
+        assert_eq!((browser.year, browser.month), (2025, 1));
+        browser.handle(Key::Previous);
+        browser.handle(Key::Previous);
+        assert_eq!((browser.year, browser.month), (2024, 11));
+        browser.handle(Key::Other);
+        assert_eq!((browser.year, browser.month), (2024, 11));
+        browser.handle(Key::Today);
+        assert_eq!((browser.year, browser.month), (2024, 3));
+        assert!(!browser.handle(Key::Quit));
+
+        // 1年1月より前、9999年12月より後には移動しない
+        let mut browser = MonthBrowser::new(1, 1, today);
+        browser.handle(Key::Previous);
+        assert_eq!((browser.year, browser.month), (1, 1));
+        let mut browser = MonthBrowser::new(9999, 12, today);
+        browser.handle(Key::Next);
+        assert_eq!((browser.year, browser.month), (9999, 12));
+    }
+}
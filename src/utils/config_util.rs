@@ -0,0 +1,164 @@
+use std::env;
+use std::path::PathBuf;
+
+/// 設定ファイルの値
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValue {
+    /// 真偽値(フラグのオプション)
+    Flag(bool),
+    /// 文字列・数値(値を取るオプション)
+    Value(String),
+}
+
+/// 設定ファイルの解析結果
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    /// オプションの既定値(オプションの長い名前, 値)
+    pub entries: Vec<(String, ConfigValue)>,
+    /// 解析できなかった行(行番号, 行の内容)
+    pub malformed: Vec<(usize, String)>,
+}
+
+impl ConfigFile {
+    /// 既定値をコマンドライン引数の形式に変換
+    /// `first-day = "monday"`は`--first-day=monday`、`week = true`は`--week`とし、`false`は省略する。
+    pub fn to_args(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter_map(|(key, value)| match value {
+                ConfigValue::Flag(true) => Some(format!("--{key}")),
+                ConfigValue::Flag(false) => None,
+                ConfigValue::Value(value) => Some(format!("--{key}={value}")),
+            })
+            .collect()
+    }
+}
+
+/// 設定ファイルのパスを取得
+/// 環境変数「CALR_CONFIG」があればそのパス、なければ設定ディレクトリの`calr/config.toml`。
+/// 設定ディレクトリはOS毎の慣習に従い、Windowsは`%APPDATA%`、macOSは`$HOME/Library/Application Support`、
+/// それ以外は`$XDG_CONFIG_HOME`(未設定の場合は`$HOME/.config`)とする。
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("CALR_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let home = || env::var_os("HOME").map(PathBuf::from);
+    let dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".config")))
+    }?;
+    Some(dir.join("calr").join("config.toml"))
+}
+
+/// 設定ファイル(TOMLの`キー = 値`の行のみ)の内容を解析
+/// 値は文字列(`"..."`・`'...'`)、整数、真偽値を受け付ける。空行と`#`以降のコメントは無視する。
+/// テーブル・配列など解析できない行は処理を中断せず、行番号とともに`malformed`に記録する。
+///
+/// * `content` - 設定ファイルの内容
+pub fn parse_config(content: &str) -> ConfigFile {
+    let mut config = ConfigFile::default();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = line.split_once('=').and_then(|(key, value)| {
+            let key = key.trim();
+            let valid_key = !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            valid_key
+                .then(|| parse_value(value.trim()))
+                .flatten()
+                .map(|value| (key.replace('_', "-"), value))
+        });
+        match entry {
+            Some(entry) => config.entries.push(entry),
+            None => config.malformed.push((i + 1, line.to_string())),
+        }
+    }
+    config
+}
+
+/// 設定ファイルの値を解析
+///
+/// * `value` - `=`の右辺(前後の空白を除いたもの)
+fn parse_value(value: &str) -> Option<ConfigValue> {
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            let (string, comment) = rest.split_once(quote)?;
+            let comment = comment.trim();
+            if !comment.is_empty() && !comment.starts_with('#') {
+                return None;
+            }
+            return Some(ConfigValue::Value(string.to_string()));
+        }
+    }
+    let value = value.split('#').next().unwrap_or_default().trim();
+    match value {
+        "true" => Some(ConfigValue::Flag(true)),
+        "false" => Some(ConfigValue::Flag(false)),
+        _ if value.parse::<i64>().is_ok() => Some(ConfigValue::Value(value.to_string())),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{parse_config, ConfigValue};
+
+    #[test]
+    fn test_parse_config() {
+        let content = "\
+# calr defaults
+first-day = \"monday\"
+color = 'always'  # always decorate
+week = true
+quiet = false
+months_per_row = 4
+
+[colors]
+locale = ja
+";
+        let config = parse_config(content);
+        assert_eq!(
+            config.entries,
+            vec![
+                (
+                    "first-day".to_string(),
+                    ConfigValue::Value("monday".to_string())
+                ),
+                (
+                    "color".to_string(),
+                    ConfigValue::Value("always".to_string())
+                ),
+                ("week".to_string(), ConfigValue::Flag(true)),
+                ("quiet".to_string(), ConfigValue::Flag(false)),
+                (
+                    "months-per-row".to_string(),
+                    ConfigValue::Value("4".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            config.malformed,
+            vec![(8, "[colors]".to_string()), (9, "locale = ja".to_string())]
+        );
+        assert_eq!(
+            config.to_args(),
+            vec![
+                "--first-day=monday",
+                "--color=always",
+                "--week",
+                "--months-per-row=4"
+            ]
+        );
+    }
+}
@@ -1,8 +1,12 @@
-use ansi_term::Style;
+use crate::utils::era_util::{format_japanese_era, format_japanese_era_year};
+use crate::utils::locale_util::Locale;
+use crate::utils::moon_util::moon_phase;
+use crate::utils::theme_util::Theme;
 use anyhow::{bail, Result};
-use chrono::{Datelike, Months, NaiveDate};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::collections::HashSet;
+use unicode_width::UnicodeWidthStr;
 
-const LINE_WIDTH: usize = 22;
 pub const MONTH_NAMES: [&str; 12] = [
     "January",
     "February",
@@ -17,80 +21,751 @@ pub const MONTH_NAMES: [&str; 12] = [
     "November",
     "December",
 ];
+pub const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sun,
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+];
+
+/// 週の行の左側に付与する列の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeekColumn {
+    /// 行の最初の日の曜日番号(日曜日=0、週の始まりの検証用)
+    DebugOffset,
+    /// 週番号(数え方を指定)
+    WeekNumber(WeekRule),
+    /// 月内の週番号(月の最初の行を1とする)
+    WeekOfMonth,
+    /// 起点日を含む週を0とした通しの週番号(`digits`は値の最大桁数)
+    WeeksSince { epoch: NaiveDate, digits: usize },
+}
+
+impl WeekColumn {
+    /// 列の値を取得
+    ///
+    /// * `row`       - 月内の行の位置(0始まり)
+    /// * `first`     - 行の最初の日
+    /// * `first_day` - 週の始まりの曜日
+    fn value(&self, row: usize, first: NaiveDate, first_day: Weekday) -> String {
+        match self {
+            WeekColumn::DebugOffset => first.weekday().num_days_from_sunday().to_string(),
+            WeekColumn::WeekNumber(rule) => rule.week_number(first, first_day).to_string(),
+            WeekColumn::WeekOfMonth => (row + 1).to_string(),
+            WeekColumn::WeeksSince { epoch, .. } => {
+                weeks_since(*epoch, first, first_day).to_string()
+            }
+        }
+    }
+}
+
+/// 対象日を含む週の始まりの日を取得
+///
+/// * `date`      - 対象日
+/// * `first_day` - 週の始まりの曜日
+fn start_of_week(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+    date - Days::new(leading_blanks(first_day, date.weekday()) as u64)
+}
+
+/// 起点日を含む週から対象日を含む週までの週数を取得(起点日より前は負の数)
+///
+/// * `epoch`     - 起点日
+/// * `date`      - 対象日
+/// * `first_day` - 週の始まりの曜日
+pub fn weeks_since(epoch: NaiveDate, date: NaiveDate, first_day: Weekday) -> i64 {
+    let days = start_of_week(date, first_day) - start_of_week(epoch, first_day);
+    days.num_days() / 7
+}
+
+/// 週番号の数え方(どの週を第1週とするか)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeekRule {
+    /// ISO 8601(最初の木曜日を含む月曜日始まりの週を第1週とする)
+    Iso,
+    /// 1月1日を含む週を第1週とする(米国式)
+    Jan1,
+    /// 年内で最初に7日揃う週を第1週とする(それより前の日は前年の最終週)
+    FirstFull,
+}
+
+impl WeekRule {
+    /// 対象日を含む週の週番号を取得
+    ///
+    /// * `date`      - 対象日
+    /// * `first_day` - 週の始まりの曜日(ISO 8601は常に月曜日始まりのため使わない)
+    pub fn week_number(&self, date: NaiveDate, first_day: Weekday) -> u32 {
+        let start = start_of_week(date, first_day);
+        match self {
+            WeekRule::Iso => date.iso_week().week(),
+            WeekRule::Jan1 => {
+                // 翌年の1月1日を含む週は翌年の第1週
+                let end = start + Days::new(6);
+                if end.year() > start.year() {
+                    return 1;
+                }
+                let jan1 = NaiveDate::from_ymd_opt(start.year(), 1, 1).unwrap();
+                let first = start_of_week(jan1, first_day);
+                (start - first).num_days() as u32 / 7 + 1
+            }
+            WeekRule::FirstFull => {
+                // 年内で最初の週の始まりの曜日
+                let first_full = |year: i32| {
+                    let jan1 = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+                    jan1 + Days::new(leading_blanks(jan1.weekday(), first_day) as u64)
+                };
+                let mut first = first_full(start.year());
+                if start < first {
+                    first = first_full(start.year() - 1);
+                }
+                (start - first).num_days() as u32 / 7 + 1
+            }
+        }
+    }
+}
+
+/// 月ヘッダの寄せ方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeaderAlign {
+    /// 左寄せ
+    Left,
+    /// 中央寄せ
+    #[default]
+    Center,
+    /// 右寄せ
+    Right,
+}
+
+impl HeaderAlign {
+    /// 表示幅(全角文字は2桁)を基準に文字列を寄せる
+    ///
+    /// * `text`  - 対象文字列
+    /// * `width` - 表示幅
+    pub fn pad(&self, text: &str, width: usize) -> String {
+        let rest = " ".repeat(width.saturating_sub(visible_width(text)));
+        match self {
+            HeaderAlign::Left => format!("{text}{rest}"),
+            HeaderAlign::Center => pad_center(text, width),
+            HeaderAlign::Right => format!("{rest}{text}"),
+        }
+    }
+}
+
+/// 週の列の幅(値2桁と区切りの空白)
+const WEEK_COLUMN_WIDTH: usize = 3;
+/// 月相の列の幅(区切りの空白と全角の記号)
+const MOON_COLUMN_WIDTH: usize = 3;
+
+/// カレンダーのフォーマット設定
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// 曜日ヘッダを3文字にして日付セルを3桁幅にするか否か
+    pub wide_weekdays: bool,
+    /// 当日以外に強調表示する日付
+    pub highlights: HashSet<NaiveDate>,
+    /// イベントのある日付(強調表示とは区別して表示)
+    pub events: HashSet<NaiveDate>,
+    /// 月名・曜日名のロケール
+    pub locale: Locale,
+    /// 週の始まりの曜日
+    pub first_day: Weekday,
+    /// 年を元号(和暦)で表示するか否か
+    pub japanese_era: bool,
+    /// 週の行の左側に付与する列
+    pub week_column: Option<WeekColumn>,
+    /// 配色
+    pub theme: Theme,
+    /// 当日を括弧で囲むか否か(当日の前後の区切りの空白を括弧に置き換える)
+    pub today_brackets: bool,
+    /// 月ヘッダを週の行の下に置くか否か
+    pub title_below: bool,
+    /// 日付に付与するハイパーリンクのURL(`{date}`を日付に置き換える)
+    pub link_format: Option<String>,
+    /// 表示を始める日(これより前の日は空白にする)
+    pub from_day: Option<u32>,
+    /// 月ヘッダの月をローマ数字で表示するか否か
+    pub roman_months: bool,
+    /// 曜日ヘッダと最初の週の間に空行を入れるか否か
+    pub header_gap: bool,
+    /// 月カレンダー1つ分の最小の幅
+    pub min_width: usize,
+    /// 各月の初日と最終日を装飾するか否か
+    pub mark_boundaries: bool,
+    /// 月ヘッダの寄せ方
+    pub header_align: HeaderAlign,
+    /// 集計の数値をロケールの桁区切り・小数点で表示するか否か
+    pub locale_number: bool,
+    /// 毎週強調表示する曜日
+    pub recur: HashSet<Weekday>,
+    /// 曜日ヘッダを省略するか否か
+    pub no_weekday_header: bool,
+    /// 週全体を強調表示する基準日(基準日の月の、基準日を含む週の日付を強調表示する)
+    pub highlight_week: Option<NaiveDate>,
+    /// 日付をアラビア・インド数字(٠〜٩)で表示するか否か
+    pub arabic_digits: bool,
+    /// 土曜日・日曜日の列を省くか否か
+    pub collapse_weekends: bool,
+    /// 年を4桁に0埋めするか否か
+    pub zero_pad_year: bool,
+    /// 1年より前の年を天文学的年表記(0年・-1年)で表示するか否か
+    pub astronomical_year: bool,
+    /// 週の行の右側に月相を付与するか否か
+    pub moon: bool,
+    /// 1週の中の日付セル同士の区切り
+    pub day_separator: String,
+    /// 日付を月毎ではなく通し番号で表示する期間(開始日, 終了日)、開始日を1とする
+    pub continuous_days: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            wide_weekdays: false,
+            highlights: HashSet::new(),
+            events: HashSet::new(),
+            locale: Locale::default(),
+            first_day: Weekday::Sun,
+            japanese_era: false,
+            week_column: None,
+            theme: Theme::default(),
+            today_brackets: false,
+            title_below: false,
+            link_format: None,
+            from_day: None,
+            roman_months: false,
+            header_gap: false,
+            min_width: 0,
+            mark_boundaries: false,
+            header_align: HeaderAlign::Center,
+            locale_number: false,
+            recur: HashSet::new(),
+            no_weekday_header: false,
+            highlight_week: None,
+            arabic_digits: false,
+            collapse_weekends: false,
+            zero_pad_year: false,
+            astronomical_year: false,
+            moon: false,
+            day_separator: String::from(" "),
+            continuous_days: None,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// 日付セル1つ分の幅
+    /// 通し番号で表示する場合は最大の番号の桁数以上とする。
+    pub fn cell_width(&self) -> usize {
+        let width = if self.wide_weekdays { 3 } else { 2 };
+        match self.continuous_days {
+            Some((start, end)) => {
+                let days = end.signed_duration_since(start).num_days() + 1;
+                width.max(days.to_string().len())
+            }
+            None => width,
+        }
+    }
+
+    /// 週の列の幅(列がない場合は0)
+    pub fn week_column_width(&self) -> usize {
+        match self.week_column {
+            Some(WeekColumn::WeeksSince { digits, .. }) => WEEK_COLUMN_WIDTH.max(digits + 1),
+            Some(_) => WEEK_COLUMN_WIDTH,
+            None => 0,
+        }
+    }
+
+    /// 曜日の列を表示するか否か(週末の列を省く場合は土曜日・日曜日を表示しない)
+    ///
+    /// * `weekday` - 曜日
+    pub fn shows_weekday(&self, weekday: Weekday) -> bool {
+        !(self.collapse_weekends && matches!(weekday, Weekday::Sat | Weekday::Sun))
+    }
+
+    /// 週の始まりの曜日から並べた、表示する曜日の列の位置毎の表示有無
+    fn shown_columns(&self) -> Vec<bool> {
+        let start = self.first_day.num_days_from_sunday() as usize;
+        (0..7)
+            .map(|i| self.shows_weekday(WEEKDAYS[(start + i) % 7]))
+            .collect()
+    }
+
+    /// 月相の列の幅(区切りの空白と月相の記号、列がない場合は0)
+    pub fn moon_column_width(&self) -> usize {
+        if self.moon {
+            MOON_COLUMN_WIDTH
+        } else {
+            0
+        }
+    }
+
+    /// 年をフォーマットする(0埋めする場合は4桁、例: `0044`)
+    ///
+    /// * `year` - 年
+    pub fn format_year(&self, year: i32) -> String {
+        if self.zero_pad_year {
+            format!("{year:04}")
+        } else {
+            year.to_string()
+        }
+    }
+
+    /// 見出しに表示する年をフォーマットする
+    /// 1年より前の年は、天文学的年表記でなければ紀元前の表記(0年は`1 BC`、-1年は`2 BC`)とする。
+    ///
+    /// * `year` - 年(先発グレゴリオ暦の天文学的年表記)
+    pub fn format_header_year(&self, year: i32) -> String {
+        if year < 1 && !self.astronomical_year {
+            format!("{} BC", self.format_year(1 - year))
+        } else {
+            self.format_year(year)
+        }
+    }
+
+    /// 集計の数値をフォーマットする(ロケールの慣習に従う場合は桁区切りも付与する)
+    ///
+    /// * `value`    - 数値
+    /// * `decimals` - 小数点以下の桁数
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        if self.locale_number {
+            self.locale.format_number(value, decimals)
+        } else {
+            format!("{value:.decimals$}")
+        }
+    }
+
+    /// 1週分の日付セルの数(週末の列を省く場合は5つ)
+    pub fn days_per_row(&self) -> usize {
+        if self.collapse_weekends {
+            5
+        } else {
+            7
+        }
+    }
+
+    /// 日付セル同士の区切りの幅
+    pub fn day_separator_width(&self) -> usize {
+        visible_width(&self.day_separator)
+    }
+
+    /// 月カレンダー1つ分の幅
+    /// 1週分の日付セルとセル間の区切りに週の列・月相の列を合わせた幅になる(月同士の区切りは含まない)。
+    /// 最小の幅に満たない場合は最小の幅とする。
+    pub fn line_width(&self) -> usize {
+        let days = self.days_per_row();
+        (self.week_column_width()
+            + self.cell_width() * days
+            + self.day_separator_width() * (days - 1)
+            + self.moon_column_width())
+        .max(self.min_width)
+    }
+}
 
 /// 対象年月の最終日を取得
 ///
 /// * `year`  - 対象年
 /// * `month` - 対象月
-fn last_day_in_month(year: i32, month: u32) -> NaiveDate {
-    let (y, m) = if month == 12 {
-        (year + 1, 1)
-    } else {
-        (year, month + 1)
-    };
-    NaiveDate::from_ymd_opt(y, m, 1)
+pub fn last_day_in_month(year: i32, month: u32) -> NaiveDate {
+    // 12月は翌年に繰り上げず直接31日を返す(最大年でも溢れないように)
+    if month == 12 {
+        return NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+    }
+    NaiveDate::from_ymd_opt(year, month + 1, 1)
         .unwrap()
         .pred_opt()
         .unwrap()
 }
 
+/// 対象年月の日数を取得
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    last_day_in_month(year, month).day()
+}
+
+/// 閏年か否かを判定
+/// 2月の最終日が29日かどうかで判定する。
+///
+/// * `year` - 対象年
+pub fn is_leap_year(year: i32) -> bool {
+    last_day_in_month(year, 2).day() == 29
+}
+
+/// 対象年月の全日付を取得
+/// 対象年月の1日から最終日までの日付を順に並べたVecを返す。
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+pub fn month_days(year: i32, month: u32) -> Vec<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let last = last_day_in_month(year, month);
+    first.iter_days().take_while(|date| *date <= last).collect()
+}
+
+/// 対象年月の営業日数を取得
+/// 月曜日〜金曜日の日付のうち、休日に含まれない日付を数える。
+///
+/// * `year_months` - 対象年月タプルのスライス
+/// * `holidays`    - 休日
+pub fn count_business_days(year_months: &[(i32, u32)], holidays: &HashSet<NaiveDate>) -> usize {
+    year_months
+        .iter()
+        .flat_map(|&(year, month)| month_days(year, month))
+        .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+        .filter(|date| !holidays.contains(date))
+        .count()
+}
+
+/// 対象年月に含まれる曜日の日数を取得
+///
+/// * `year`    - 対象年
+/// * `month`   - 対象月
+/// * `weekday` - 対象曜日
+pub fn count_weekday(year: i32, month: u32, weekday: Weekday) -> usize {
+    month_days(year, month)
+        .into_iter()
+        .filter(|date| date.weekday() == weekday)
+        .count()
+}
+
+/// 曜日の日数を出力形式にフォーマットする
+/// 例: `Fridays in March 2024: 5`
+///
+/// * `year`    - 対象年
+/// * `month`   - 対象月
+/// * `weekday` - 対象曜日
+pub fn format_weekday_count(year: i32, month: u32, weekday: Weekday) -> String {
+    format!(
+        "{}s in {} {year}: {}",
+        WEEKDAY_NAMES[weekday.num_days_from_sunday() as usize],
+        MONTH_NAMES[month as usize - 1],
+        count_weekday(year, month, weekday)
+    )
+}
+
+/// 対象年月に含まれる週末(土曜日・日曜日)の日数を取得
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+pub fn count_weekend_days(year: i32, month: u32) -> usize {
+    count_weekday(year, month, Weekday::Sat) + count_weekday(year, month, Weekday::Sun)
+}
+
+/// 週末(土曜日・日曜日)の日数を出力形式にフォーマットする
+/// 例: `Weekend days in March 2024: 10`
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+pub fn format_weekend_count(year: i32, month: u32) -> String {
+    format!(
+        "Weekend days in {} {year}: {}",
+        MONTH_NAMES[month as usize - 1],
+        count_weekend_days(year, month)
+    )
+}
+
+/// 年ヘッダに表示する年をフォーマットする
+/// 元号表示の場合、月を指定すると月初の元号、指定しない場合は年全体の元号で表示する。
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+/// * `opts`  - フォーマット設定
+pub fn format_year_label(year: i32, month: Option<u32>, opts: &FormatOptions) -> String {
+    let label = match (opts.japanese_era, month) {
+        (false, _) => None,
+        (true, Some(month)) => {
+            format_japanese_era(NaiveDate::from_ymd_opt(year, month, 1).unwrap())
+        }
+        (true, None) => format_japanese_era_year(year),
+    };
+    label.unwrap_or_else(|| opts.format_header_year(year))
+}
+
+/// 文字列の表示幅を取得
+/// 全角文字は2桁とし、装飾のエスケープシーケンス(`ESC [ ... m`)と
+/// ハイパーリンクのエスケープシーケンス(`ESC ] ... ESC \`)は幅に含めない。
+///
+/// * `text` - 対象文字列
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+    while let Some(start) = rest.find('\u{1b}') {
+        width += rest[..start].width();
+        rest = &rest[start..];
+        let end = if rest.starts_with("\u{1b}]") {
+            rest[1..].find("\u{1b}\\").map(|end| end + 3)
+        } else {
+            rest.find('m').map(|end| end + 1)
+        };
+        rest = end.map_or("", |end| &rest[end..]);
+    }
+    width + rest.width()
+}
+
+/// 表示幅(全角文字は2桁)を基準に文字列を中央寄せする
+///
+/// * `text`  - 対象文字列
+/// * `width` - 表示幅
+pub fn pad_center(text: &str, width: usize) -> String {
+    let rest = width.saturating_sub(visible_width(text));
+    let left = rest / 2;
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(rest - left))
+}
+
+/// 月初までの空白セル数を取得
+/// 週の始まりの曜日から月初の曜日まで何日あるかを返す。
+///
+/// * `first_day_of_week`   - 週の始まりの曜日
+/// * `month_first_weekday` - 月初(1日)の曜日
+pub fn leading_blanks(first_day_of_week: Weekday, month_first_weekday: Weekday) -> usize {
+    month_first_weekday.days_since(first_day_of_week) as usize
+}
+
+/// 対象月の週の行数を取得
+/// 月初までの空白セルと日数の合計を7日毎に区切った行数(4〜6)を返す。
+///
+/// * `year`      - 対象年
+/// * `month`     - 対象月
+/// * `first_day` - 週の始まりの曜日
+pub fn week_rows(year: i32, month: u32, first_day: Weekday) -> usize {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    (leading_blanks(first_day, first.weekday()) + month_days(year, month).len()).div_ceil(7)
+}
+
 /// 対象月をカレンダー形式フォーマットする
 ///
 /// * `year`  - 対象年
 /// * `month` - 対象月
 /// * `add_year` - 年ヘッダを追加するか否か
 /// * `today` - 当日日付
-pub fn format_month(year: i32, month: u32, add_year: bool, today: NaiveDate) -> Vec<String> {
-    let is_today = |day: u32| year == today.year() && month == today.month() && day == today.day();
+/// * `opts`  - フォーマット設定
+pub fn format_month(
+    year: i32,
+    month: u32,
+    add_year: bool,
+    today: NaiveDate,
+    opts: &FormatOptions,
+) -> Vec<String> {
+    // 月ヘッダ
+    let month_name = if opts.roman_months {
+        to_roman(month)
+    } else {
+        opts.locale.month_names()[month as usize - 1].to_string()
+    };
+    let title = if add_year {
+        format!(
+            "{month_name} {}",
+            format_year_label(year, Some(month), opts)
+        )
+    } else {
+        month_name
+    };
 
-    // 月ヘッダを行に追加
-    let month_name = MONTH_NAMES[month as usize - 1];
-    let mut lines = Vec::with_capacity(8);
-    lines.push(format!(
-        "{:^20}  ", // two trailing spaces
-        if add_year {
-            format!("{month_name} {year}")
-        } else {
-            month_name.to_string()
+    // 通し番号で表示する場合は期間の開始日からの日数を表示する
+    let days: Vec<_> = month_days(year, month)
+        .into_iter()
+        .map(|date| match opts.continuous_days {
+            Some((start, _)) => (
+                date,
+                date.signed_duration_since(start).num_days() as u32 + 1,
+            ),
+            None => (date, date.day()),
+        })
+        .collect();
+    format_grid(&title, &days, today, opts)
+}
+
+/// 数をローマ数字に変換する
+/// 例: `4` → `IV`、`12` → `XII`(1〜3999)
+///
+/// * `number` - 対象の数
+pub fn to_roman(number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut rest = number;
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while rest >= value {
+            roman.push_str(numeral);
+            rest -= value;
         }
+    }
+    roman
+}
+
+/// 数をアラビア・インド数字に変換する
+/// 例: `24` → `٢٤`(各桁を1文字に置き換えるため桁数は変わらない)
+///
+/// * `number` - 対象の数
+pub fn to_arabic_indic(number: u32) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|digit| char::from_u32('\u{660}' as u32 + digit.to_digit(10).unwrap()).unwrap())
+        .collect()
+}
+
+/// 月ヘッダ・曜日ヘッダ・日付の行からなるカレンダーの枠組みをフォーマットする
+/// 暦法に依存しないよう、各日はグレゴリオ暦の日付(強調表示の判定用)と
+/// 表示する日の数字の組で受け取る。
+///
+/// * `title` - 月ヘッダ
+/// * `days`  - 月初から月末までの(日付, 表示する日)のスライス
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+pub fn format_grid(
+    title: &str,
+    days: &[(NaiveDate, u32)],
+    today: NaiveDate,
+    opts: &FormatOptions,
+) -> Vec<String> {
+    let cell_width = opts.cell_width();
+    let line_width = opts.line_width();
+    let column_blank = " ".repeat(opts.week_column_width());
+    let moon_blank = " ".repeat(opts.moon_column_width());
+    let grid_width = line_width - column_blank.len() - moon_blank.len();
+
+    // 月カレンダー1つ分の行数(月ヘッダ + 曜日ヘッダ(省略可) + 曜日ヘッダ下の空行 + 最大6週)
+    let height = 7 + usize::from(!opts.no_weekday_header) + usize::from(opts.header_gap);
+
+    // 月ヘッダを行に追加(週の列を除いた幅で寄せる、既定は中央寄せ)
+    let mut lines = Vec::with_capacity(height);
+    let header_style = opts.theme.header_style();
+    lines.push(format!(
+        "{column_blank}{}{moon_blank}",
+        opts.header_align
+            .pad(&header_style.paint(title).to_string(), grid_width)
     ));
 
-    // 曜日ヘッダを行に追加
-    lines.push("Su Mo Tu We Th Fr Sa  ".to_string()); // two trailing spaces
+    // 曜日ヘッダを行に追加(オプション「--no-weekday-header」の場合は省略)
+    if !opts.no_weekday_header {
+        let weekday_header = format_weekday_header(opts);
+        lines.push(format!(
+            "{column_blank}{}{}{moon_blank}",
+            header_style.paint(&weekday_header),
+            " ".repeat(grid_width.saturating_sub(visible_width(&weekday_header)))
+        ));
+    }
+    if opts.header_gap {
+        lines.push(" ".repeat(line_width));
+    }
 
     // 対象期間のカレンダーを生成
-    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-    let last = last_day_in_month(year, month);
-    let mut days: Vec<String> = (1..first.weekday().number_from_sunday())
-        .map(|_| "  ".to_string()) // two spaces
-        .collect();
+    let first_weekday = days
+        .first()
+        .map_or(opts.first_day, |(date, _)| date.weekday());
+    let leading = leading_blanks(opts.first_day, first_weekday);
+    let mut cells: Vec<String> = (0..leading).map(|_| " ".repeat(cell_width)).collect();
 
-    days.extend((first.day()..=last.day()).map(|num| {
-        let fmt = format!("{num:>2}");
-        if is_today(num) {
-            Style::new().reverse().paint(fmt).to_string()
+    let last = days.len().saturating_sub(1);
+    cells.extend(days.iter().enumerate().map(|(i, &(date, number))| {
+        // 表示を始める日より前の日は月初前と同じく空白にする
+        if opts.from_day.is_some_and(|from_day| number < from_day) {
+            " ".repeat(cell_width)
         } else {
-            fmt
+            format_day(date, number, i == 0 || i == last, today, opts)
         }
     }));
 
     // 対象カレンダーを週単位(7日毎)に分割
-    for week in days.chunks(7) {
+    // 強調表示のエスケープシーケンスを幅に含めないよう、表示幅から空白埋めを算出する
+    // 週末の列を省く場合は表示する曜日のセルのみ残し、月の日付が残らない行は出力しない
+    let today_index = days
+        .iter()
+        .position(|&(date, _)| is_today(date, today))
+        .map(|index| index + leading);
+    let shown = opts.shown_columns();
+    let mut row = 0;
+    for (i, week) in cells.chunks(7).enumerate() {
+        let visible: Vec<usize> = (0..week.len()).filter(|&j| shown[j]).collect();
+        let has_days = visible.iter().any(|&j| {
+            (i * 7 + j)
+                .checked_sub(leading)
+                .is_some_and(|index| index < days.len())
+        });
+        if !has_days {
+            continue;
+        }
+        let today = today_index
+            .filter(|index| index / 7 == i)
+            .and_then(|index| visible.iter().position(|&j| j == index % 7));
+        let week: Vec<String> = visible.iter().map(|&j| week[j].clone()).collect();
+        let line = join_cells(&week, today, opts);
+        let column = match opts.week_column {
+            Some(week_column) => {
+                let (first, _) = days[(i * 7).saturating_sub(leading)];
+                let value = week_column.value(row, first, opts.first_day);
+                let value = format!("{value:>0$}", opts.week_column_width() - 1);
+                format!("{} ", opts.theme.week_number_style().paint(value))
+            }
+            None => String::new(),
+        };
+        // 月相は週の中日(週の始まりから4日目)で求める
+        let moon = if opts.moon {
+            let midpoint = days[0].0 - Days::new(leading as u64) + Days::new((i * 7 + 3) as u64);
+            format!(" {}", moon_phase(midpoint))
+        } else {
+            String::new()
+        };
+        // 当日の括弧が行の右端からはみ出す場合は、月相の前の空白を括弧に使う
+        let overflow = visible_width(&line).saturating_sub(grid_width);
         lines.push(format!(
-            "{:width$}  ", // two trailing spaces
-            week.join(" "),
-            width = LINE_WIDTH - 2
+            "{column}{line}{}{}",
+            " ".repeat(grid_width.saturating_sub(visible_width(&line))),
+            &moon[overflow.min(moon.len())..]
         ));
+        row += 1;
     }
 
     // 空行補完
-    while lines.len() < 8 {
-        lines.push(" ".repeat(LINE_WIDTH));
+    while lines.len() < height {
+        lines.push(" ".repeat(line_width));
+    }
+
+    // 月ヘッダを末尾に移動(行数は変えない)
+    if opts.title_below {
+        lines.rotate_left(1);
     }
 
     lines
 }
 
+/// 対象年月からdeltaヶ月ずらした年月を取得
+/// 日付は扱わず、年月だけを算出して(年, 月)のタプルで返す。負のdeltaは過去方向になる。
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+/// * `delta` - ずらす月数
+pub fn first_of_month_offset(year: i32, month: u32, delta: i32) -> (i32, u32) {
+    let index = year * 12 + (month as i32 - 1) + delta;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
 /// 対象年月のnヶ月前の日付取得
 /// 対象年月のnヶ月前を算出して、その年月の1日の日付を返す。
 ///
@@ -98,9 +773,8 @@ pub fn format_month(year: i32, month: u32, add_year: bool, today: NaiveDate) ->
 /// * `year`  - 対象年
 /// * `month` - 対象月
 pub fn get_before_month(n: u32, year: i32, month: u32) -> NaiveDate {
-    let target_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-
-    target_date - Months::new(n)
+    let (year, month) = first_of_month_offset(year, month, -(n as i32));
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap()
 }
 
 /// 対象年月のnヶ月後の日付取得
@@ -110,116 +784,497 @@ pub fn get_before_month(n: u32, year: i32, month: u32) -> NaiveDate {
 /// * `year`  - 対象年
 /// * `month` - 対象月
 pub fn get_after_month(n: u32, year: i32, month: u32) -> NaiveDate {
-    let target_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-    let after_month_first = target_date + Months::new(n);
-
-    NaiveDate::from_ymd_opt(
-        after_month_first.year(),
-        after_month_first.month(),
-        get_days_from_ym(after_month_first.year(), after_month_first.month()),
-    )
-    .unwrap()
+    let (year, month) = first_of_month_offset(year, month, n as i32);
+    NaiveDate::from_ymd_opt(year, month, days_in_month(year, month)).unwrap()
 }
 
-/// 対象年月の日数取得
-/// 対象年月の日数を算出して返す。
+/// 開始日から終了日が含まれる年月を取得
+/// 期間に含まれる月初めの日付を1ヶ月ずつ辿り、年月だけに加工したVecを返す。
 ///
-/// * `year`  - 対象年
-/// * `month` - 対象月
-fn get_days_from_ym(year: i32, month: u32) -> u32 {
-    let days = NaiveDate::from_ymd_opt(
-        match month {
-            12 => year + 1,
-            _ => year,
-        },
-        match month {
-            12 => 1,
-            _ => month + 1,
-        },
-        1,
-    )
-    .unwrap()
-    .signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1).unwrap())
-    .num_days();
-    TryFrom::try_from(days).unwrap()
+/// * `start_date`  - 開始日
+/// * `end_date`    - 終了日
+pub fn get_year_month(start_date: NaiveDate, end_date: NaiveDate) -> Vec<(i32, u32)> {
+    // 開始日以降の最初の月初から1ヶ月ずつ進める
+    let delta = if start_date.day() == 1 { 0 } else { 1 };
+    let first = first_of_month_offset(start_date.year(), start_date.month(), delta);
+    (0..)
+        .map(|i| first_of_month_offset(first.0, first.1, i))
+        .take_while(|&(year, month)| {
+            NaiveDate::from_ymd_opt(year, month, 1).is_some_and(|date| date <= end_date)
+        })
+        .collect()
 }
 
-/// 開始日から終了日が含まれる年月を取得
-/// カレンダーに含まれる月初めの日付だけをフィルタリング後、年月だけに加工したVecを返す。
+/// 期間の日数をフォーマットする
+/// 開始日から終了日までの日数(両端を含む)を算出して、出力形式に加工した文字列を返す。
+/// 例: `92 days (2024-03-01 to 2024-05-31)`
 ///
 /// * `start_date`  - 開始日
 /// * `end_date`    - 終了日
-pub fn get_year_month(start_date: NaiveDate, end_date: NaiveDate) -> Vec<(i32, u32)> {
-    let calendar: Vec<NaiveDate> = (0..)
-        .map(|i| start_date + chrono::Duration::days(i))
-        .take_while(|date| *date <= end_date)
-        .collect();
+/// * `opts`        - フォーマット設定
+pub fn format_summary(start_date: NaiveDate, end_date: NaiveDate, opts: &FormatOptions) -> String {
+    let days = end_date.signed_duration_since(start_date).num_days() + 1;
+    format!(
+        "{} days ({start_date} to {end_date})",
+        opts.format_number(days as f64, 0)
+    )
+}
 
-    // カレンダーに含まれる月初めの日付だけをフィルタリング後、年月だけに加工したVecを返す
-    let year_month: Vec<(i32, u32)> = calendar
-        .into_iter()
-        .filter(|date| date.day() == 1) // 月初の日付のみ
-        .map(|date| (date.year(), date.month()))
-        .collect();
+/// 2つの日付の間の日数をフォーマットする
+/// 両端を含む日数と含まない日数を返す。日付が逆順の場合は並べ替えて末尾に` (reversed)`を付与する。
+/// 例: `2024-01-01 to 2024-12-31: 366 days inclusive, 365 days exclusive`
+///
+/// * `from` - 開始日
+/// * `to`   - 終了日
+/// * `opts` - フォーマット設定
+pub fn format_days_between(from: NaiveDate, to: NaiveDate, opts: &FormatOptions) -> String {
+    let (start, end) = if from <= to { (from, to) } else { (to, from) };
+    let days = end.signed_duration_since(start).num_days();
+    let line = format!(
+        "{start} to {end}: {} inclusive, {} exclusive",
+        format_count(days + 1, "day", opts),
+        format_count(days, "day", opts)
+    );
+    if from <= to {
+        line
+    } else {
+        format!("{line} (reversed)")
+    }
+}
 
-    year_month
+/// 数と単位をフォーマットする(数が1の場合は単数形)
+/// 例: `1 month`、`12 months`
+///
+/// * `count` - 数
+/// * `unit`  - 単位(単数形)
+/// * `opts`  - フォーマット設定
+fn format_count(count: i64, unit: &str, opts: &FormatOptions) -> String {
+    let suffix = if count == 1 { "" } else { "s" };
+    format!("{} {unit}{suffix}", opts.format_number(count as f64, 0))
 }
 
-/// 全ての年が同じか確認
-/// 対象年月から年だけを取り出して、最初の年と同じかを比較する。
+/// 期間の月数をフォーマットする
+/// 開始月から終了月までの月数(両端を含む)を年月の通し番号の差から算出する。逆順の指定は並べ替えて注記する。
+/// 年は見出しと同じく「--zero-pad-year」に従う。
+/// 例: `2024-01 to 2024-12: 12 months`
 ///
-/// * `year_months`    - 対象年月タプルのVec
-pub fn is_all_same_year(year_months: Vec<(i32, u32)>) -> bool {
-    let all_same_year = year_months
-        .iter()
-        .map(|(year, _)| year) // 年だけを取り出す
-        .all(|&year| year == year_months[0].0); // 最初の年と比較
+/// * `from` - 開始年月
+/// * `to`   - 終了年月
+/// * `opts` - フォーマット設定
+pub fn format_months_between(from: (i32, u32), to: (i32, u32), opts: &FormatOptions) -> String {
+    let (start, end) = if from <= to { (from, to) } else { (to, from) };
+    let months = (end.0 - start.0) * 12 + end.1 as i32 - start.1 as i32 + 1;
+    let line = format!(
+        "{}-{:02} to {}-{:02}: {}",
+        opts.format_year(start.0),
+        start.1,
+        opts.format_year(end.0),
+        end.1,
+        format_count(months.into(), "month", opts)
+    );
+    if from <= to {
+        line
+    } else {
+        format!("{line} (reversed)")
+    }
+}
 
-    all_same_year
+/// 年の進捗をフォーマットする
+/// 対象日が年の何日目にあたるかと、年の日数に対する割合を返す。
+/// 例: `Day 98 of 366 (26.8%)`
+///
+/// * `date` - 対象日
+/// * `opts` - フォーマット設定
+pub fn format_year_progress(date: NaiveDate, opts: &FormatOptions) -> String {
+    let days = if is_leap_year(date.year()) { 366 } else { 365 };
+    let ordinal = date.ordinal();
+    format!(
+        "Day {ordinal} of {days} ({}%)",
+        opts.format_number(ordinal as f64 / days as f64 * 100.0, 1)
+    )
 }
 
-/// カレンダー生成
-/// 対象年月からカレンダーを生成する。
+/// 対象日が当日か否かを判定
 ///
-/// * `year_months`    - 対象年月タプルのVec
-/// * `all_same_year`  - 全ての年月が同じ年か否か
-/// * `today`          - 当日日付
-pub fn get_calendar(
-    year_months: Vec<(i32, u32)>,
-    all_same_year: bool,
+/// * `date`  - 対象日
+/// * `today` - 当日日付
+pub fn is_today(date: NaiveDate, today: NaiveDate) -> bool {
+    date == today
+}
+
+/// 対象日が強調表示する日付か否かを判定
+///
+/// * `date`       - 対象日
+/// * `highlights` - 強調表示する日付
+pub fn is_highlighted(date: NaiveDate, highlights: &HashSet<NaiveDate>) -> bool {
+    highlights.contains(&date)
+}
+
+/// 日付セル1つ分をフォーマットする
+/// 当日・強調表示する日付・繰り返しの曜日・月の初日と最終日・週末・イベントのある日付を配色に従って装飾する。
+///
+/// * `date`     - 対象日
+/// * `number`   - 表示する日
+/// * `boundary` - 月の初日または最終日か否か
+/// * `today`    - 当日日付
+/// * `opts`     - フォーマット設定
+fn format_day(
+    date: NaiveDate,
+    number: u32,
+    boundary: bool,
     today: NaiveDate,
-) -> Vec<Vec<String>> {
-    // カレンダー生成
-    let calendar: Vec<_> = year_months
-        .clone()
-        .into_iter()
-        .map(|(year, month)| format_month(year, month, all_same_year, today))
-        .collect();
-    calendar
+    opts: &FormatOptions,
+) -> String {
+    let cell_width = opts.cell_width();
+    let theme = &opts.theme;
+    let mut style = if is_today(date, today) {
+        theme.today_style()
+    } else if is_highlighted(date, &opts.highlights) || is_in_highlighted_week(date, opts) {
+        theme.highlight_style()
+    } else if opts.recur.contains(&date.weekday()) {
+        theme.recur_style()
+    } else if boundary && opts.mark_boundaries {
+        theme.boundary_style()
+    } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        theme.weekend_style()
+    } else {
+        Default::default()
+    };
+    if opts.events.contains(&date) {
+        style = theme.with_event_style(style);
+    }
+    let number = if opts.arabic_digits {
+        to_arabic_indic(number)
+    } else {
+        number.to_string()
+    };
+    let text = match opts.link_format {
+        // 桁揃えの空白はリンクに含めない
+        Some(ref link_format) => {
+            let url = link_format.replace("{date}", &date.format("%Y-%m-%d").to_string());
+            let padding = cell_width.saturating_sub(number.chars().count());
+            format!(
+                "{}\u{1b}]8;;{url}\u{1b}\\{number}\u{1b}]8;;\u{1b}\\",
+                " ".repeat(padding)
+            )
+        }
+        None => format!("{number:>cell_width$}"),
+    };
+    style.paint(text).to_string()
 }
 
-/// 引数・月の解析
-/// 受け取った月(数値 or 文字列)を解析してu32型に変換して返す。
+/// 週全体を強調表示する週の日付か否か
+/// 基準日と同じ月で、基準日と同じ週(週の始まりの曜日から7日間)に含まれる日付を対象とする。
 ///
-/// * `year_months`    - 対象年月タプルのVec
-/// * `all_same_year`  - 全ての年月が同じ年か否か
-/// * `today`          - 当日日付
-pub fn parse_month(month: String) -> Result<u32> {
-    match month.parse() {
-        Ok(num) => {
-            if (1..=12).contains(&num) {
-                Ok(num)
-            } else {
-                bail!(r#"month "{month}" not in the range 1 through 12"#)
+/// * `date` - 対象日
+/// * `opts` - フォーマット設定
+fn is_in_highlighted_week(date: NaiveDate, opts: &FormatOptions) -> bool {
+    opts.highlight_week.is_some_and(|base| {
+        (date.year(), date.month()) == (base.year(), base.month())
+            && start_of_week(date, opts.first_day) == start_of_week(base, opts.first_day)
+    })
+}
+
+/// 1週分の日付セルを区切りで連結する
+/// 当日を括弧で囲む場合、当日のセルの前後の区切りの当日側の1文字を括弧に置き換えて桁を揃える。
+/// 行の先頭は、1桁の日付ならセルの空白埋めを括弧に使い、2桁の日付なら括弧を省く。
+/// 行の末尾は、1桁の日付ならセルの空白埋めを詰め、2桁の日付なら行の後ろ(月同士の区切り)に括弧を置く。
+///
+/// * `cells` - 1週分の日付セル
+/// * `today` - 当日のセルの位置
+/// * `opts`  - フォーマット設定
+fn join_cells(cells: &[String], today: Option<usize>, opts: &FormatOptions) -> String {
+    let mut cells = cells.to_vec();
+    // 括弧は区切りの当日側の端の1文字と置き換える
+    let separator = opts.day_separator.as_str();
+    let mut chars = separator.chars();
+    chars.next_back();
+    let opening = format!("{}[", chars.as_str());
+    let mut chars = separator.chars();
+    chars.next();
+    let closing_separator = format!("]{}", chars.as_str());
+    let mut separators = vec![separator; cells.len().saturating_sub(1)];
+    let mut closing = "";
+    if let Some(i) = today.filter(|_| opts.today_brackets) {
+        let padded = cells[i].starts_with(' ');
+        match (i > 0, i + 1 < cells.len()) {
+            (true, true) => {
+                separators[i - 1] = &opening;
+                separators[i] = &closing_separator;
+            }
+            (false, true) => {
+                if padded {
+                    cells[i].replace_range(..1, "[");
+                }
+                separators[i] = &closing_separator;
+            }
+            (true, false) => {
+                separators[i - 1] = &opening;
+                if padded && cells.len() == opts.days_per_row() {
+                    cells[i] = format!("{}]", &cells[i][1..]);
+                } else {
+                    // 週の途中で終わる行は後ろの空白埋め、それ以外は月同士の区切りを括弧に使う
+                    closing = "]";
+                }
+            }
+            (false, false) => {
+                if padded {
+                    cells[i].replace_range(..1, "[");
+                }
+                closing = "]";
+            }
+        }
+    }
+
+    // 空白のセル(月初前など)の後の区切りは空白にする
+    let blank = " ".repeat(opts.day_separator_width());
+    let mut line = String::new();
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            if cells[i - 1].trim().is_empty() && separators[i - 1] == separator {
+                line.push_str(&blank);
+            } else {
+                line.push_str(separators[i - 1]);
+            }
+        }
+        line.push_str(cell);
+    }
+    line + closing
+}
+
+/// 曜日ヘッダ行をフォーマットする
+/// 週の始まりの曜日から並べたロケールの曜日名を日付セルの幅に切り詰めて返す。
+///
+/// * `opts` - フォーマット設定
+fn format_weekday_header(opts: &FormatOptions) -> String {
+    let cell_width = opts.cell_width();
+    let mut weekday_names = opts.locale.weekday_names();
+    weekday_names.rotate_left(opts.first_day.num_days_from_sunday() as usize);
+    let weekdays: Vec<String> = weekday_names
+        .iter()
+        .zip(opts.shown_columns())
+        .filter(|&(_, shown)| shown)
+        .map(|(name, _)| {
+            format!(
+                "{:<cell_width$}",
+                name.chars().take(cell_width).collect::<String>()
+            )
+        })
+        .collect();
+    weekdays.join(&" ".repeat(opts.day_separator_width()))
+}
+
+/// 対象日を含む1週間をフォーマットする
+/// 曜日ヘッダと、週の始まりの曜日から7日分の日付の行を返す。
+/// 月をまたぐ週は両方の月の日付を含む。
+///
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+pub fn format_week(today: NaiveDate, opts: &FormatOptions) -> Vec<String> {
+    let start = today - Days::new(leading_blanks(opts.first_day, today.weekday()) as u64);
+    let days: Vec<String> = start
+        .iter_days()
+        .take(7)
+        .map(|date| {
+            let boundary = date.day() == 1 || date == last_day_in_month(date.year(), date.month());
+            format_day(date, date.day(), boundary, today, opts)
+        })
+        .collect();
+    let today = leading_blanks(opts.first_day, today.weekday());
+    vec![
+        format_weekday_header(opts),
+        join_cells(&days, Some(today), opts),
+    ]
+}
+
+/// 当日を含む週の行をフォーマットする
+/// 曜日ヘッダと、当日の月のカレンダーのうち当日を含む行を返す。
+/// `format_week`と異なり、前後の月の日付は空白になる。
+///
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+pub fn format_peek(today: NaiveDate, opts: &FormatOptions) -> Vec<String> {
+    let opts = FormatOptions {
+        title_below: false,
+        header_gap: false,
+        no_weekday_header: false,
+        ..opts.clone()
+    };
+    let lines = format_month(today.year(), today.month(), false, today, &opts);
+    let first = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
+    let row = (leading_blanks(opts.first_day, first.weekday()) + today.day0() as usize) / 7;
+    [&lines[1], &lines[2 + row]]
+        .iter()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/// ISO週日付をフォーマットする
+/// 対象日のISO週番号と曜日(月曜日=1)から`YYYY-Www-D`形式の文字列を返す。
+/// 年は暦年ではなくISO週の年を用いる。
+///
+/// * `date` - 対象日
+pub fn format_iso_week(date: NaiveDate) -> String {
+    date.format("%G-W%V-%u").to_string()
+}
+
+/// アジェンダ形式の1行をフォーマットする
+/// 例: `Mon 2024-03-04`(強調表示する日付は末尾に` *`を付与)
+///
+/// * `date`        - 対象日
+/// * `highlighted` - 強調表示するか否か
+pub fn format_agenda_line(date: NaiveDate, highlighted: bool) -> String {
+    let line = date.format("%a %Y-%m-%d").to_string();
+    if highlighted {
+        format!("{line} *")
+    } else {
+        line
+    }
+}
+
+/// 対象年月をTSV形式(1行1週、タブ区切り)でフォーマットする
+/// 先頭に見出し行を置き、各行は年・月に続けて週の始まりの曜日から7日分のセルを並べる。
+/// 月初前・月末後のセルは空にする。
+///
+/// * `year`  - 対象年
+/// * `month` - 対象月
+/// * `opts`  - フォーマット設定
+pub fn format_tsv_month(year: i32, month: u32, opts: &FormatOptions) -> Vec<String> {
+    let mut weekday_names = opts.locale.weekday_names();
+    weekday_names.rotate_left(opts.first_day.num_days_from_sunday() as usize);
+    let header: Vec<String> = weekday_names
+        .iter()
+        .map(|name| name.chars().take(opts.cell_width()).collect())
+        .collect();
+    let mut lines = vec![format!("year\tmonth\t{}", header.join("\t"))];
+
+    let days = month_days(year, month);
+    let leading = leading_blanks(opts.first_day, days[0].weekday());
+    let mut cells: Vec<String> = vec![String::new(); leading];
+    cells.extend(days.iter().map(|date| date.day().to_string()));
+    for week in cells.chunks(7) {
+        let mut week = week.to_vec();
+        week.resize(7, String::new());
+        lines.push(format!(
+            "{}\t{month}\t{}",
+            opts.format_year(year),
+            week.join("\t")
+        ));
+    }
+    lines
+}
+
+/// NDJSON形式の1行(1日分のレコード)をフォーマットする
+/// 例: `{"date":"2024-03-04","weekday":"Mon","today":false,"day_of_year":64,"iso_week":10}`
+///
+/// * `date`  - 対象日
+/// * `today` - 当日日付
+pub fn format_ndjson_line(date: NaiveDate, today: NaiveDate) -> String {
+    format!(
+        r#"{{"date":"{}","weekday":"{}","today":{},"day_of_year":{},"iso_week":{}}}"#,
+        date.format("%Y-%m-%d"),
+        date.format("%a"),
+        is_today(date, today),
+        date.ordinal(),
+        date.iso_week().week()
+    )
+}
+
+/// 2つの年で曜日が異なる日付を取得
+/// 対象年の対象月の各日について、比較年の同じ日付と曜日が異なる
+/// (または比較年にその日付が存在しない)日付を返す。
+///
+/// * `month`      - 対象月
+/// * `year`       - 対象年
+/// * `other_year` - 比較年
+pub fn weekday_diff_days(month: u32, year: i32, other_year: i32) -> HashSet<NaiveDate> {
+    month_days(year, month)
+        .into_iter()
+        .filter(|date| {
+            NaiveDate::from_ymd_opt(other_year, month, date.day())
+                .is_none_or(|other| other.weekday() != date.weekday())
+        })
+        .collect()
+}
+
+/// 全ての年が同じか確認
+/// 対象年月から年だけを取り出して、最初の年と同じかを比較する。
+///
+/// * `year_months`    - 対象年月タプルのVec
+pub fn is_all_same_year(year_months: Vec<(i32, u32)>) -> bool {
+    let all_same_year = year_months
+        .iter()
+        .map(|(year, _)| year) // 年だけを取り出す
+        .all(|&year| year == year_months[0].0); // 最初の年と比較
+
+    all_same_year
+}
+
+/// カレンダー生成
+/// 対象年月からカレンダーを生成する。
+///
+/// * `year_months`    - 対象年月タプルのVec
+/// * `all_same_year`  - 全ての年月が同じ年か否か
+/// * `today`          - 当日日付
+/// * `opts`           - フォーマット設定
+pub fn get_calendar(
+    year_months: Vec<(i32, u32)>,
+    all_same_year: bool,
+    today: NaiveDate,
+    opts: &FormatOptions,
+) -> Vec<Vec<String>> {
+    // カレンダー生成
+    let calendar: Vec<_> = year_months
+        .clone()
+        .into_iter()
+        .map(|(year, month)| format_month(year, month, all_same_year, today, opts))
+        .collect();
+    calendar
+}
+
+/// 引数・月の解析
+/// 受け取った月(数値 or 英語の月名)を解析してu32型に変換して返す。
+///
+/// * `month` - 月
+pub fn parse_month(month: String) -> Result<u32> {
+    parse_localized_month(month, &Locale::default(), false)
+}
+
+/// 引数・月の解析(ロケール指定)
+/// 受け取った月(数値 or ロケールの月名)を解析してu32型に変換して返す。
+/// 厳密モードでは月名の前方一致を認めず、完全な月名のみ受け付ける。
+///
+/// * `month`  - 月
+/// * `locale` - 月名のロケール
+/// * `strict` - 厳密モードか否か
+pub fn parse_localized_month(month: String, locale: &Locale, strict: bool) -> Result<u32> {
+    // 空文字列は全ての月名に前方一致してしまうため先に弾く
+    if month.trim().is_empty() {
+        bail!("empty month name");
+    }
+
+    match month.parse() {
+        Ok(num) => {
+            if (1..=12).contains(&num) {
+                Ok(num)
+            } else {
+                bail!(r#"month "{month}" not in the range 1 through 12"#)
             }
         }
         _ => {
             let lower = &month.to_lowercase();
-            let matches: Vec<_> = MONTH_NAMES
+            let matches: Vec<_> = locale
+                .month_names()
                 .iter()
                 .enumerate()
                 .filter_map(|(i, name)| {
-                    if name.to_lowercase().starts_with(lower) {
+                    let name = name.to_lowercase();
+                    let matched = if strict {
+                        name == *lower
+                    } else {
+                        name.starts_with(lower)
+                    };
+                    if matched {
                         Some(i + 1)
                     } else {
                         None
@@ -236,54 +1291,243 @@ pub fn parse_month(month: String) -> Result<u32> {
     }
 }
 
+/// 引数・年月の解析
+/// 受け取った年月(YYYY-MM)を解析して(年, 月)のタプルに変換して返す。
+///
+/// * `year_month` - 年月
+pub fn parse_year_month(year_month: String) -> Result<(i32, u32)> {
+    let parsed = year_month
+        .split_once('-')
+        .and_then(|(year, month)| Some((year.parse::<i32>().ok()?, month.parse::<u32>().ok()?)));
+
+    match parsed {
+        Some((year, month)) if (1..=9999).contains(&year) && (1..=12).contains(&month) => {
+            Ok((year, month))
+        }
+        _ => bail!(r#"Invalid year-month "{year_month}""#),
+    }
+}
+
+/// 引数・年の範囲の解析
+/// 受け取った年の範囲(YYYY-YYYY)を解析して範囲内(両端を含む)の年を返す。
+///
+/// * `range` - 年の範囲
+pub fn parse_year_range(range: String) -> Result<Vec<i32>> {
+    let years = range
+        .split_once('-')
+        .and_then(|(start, end)| Some((start.parse::<i32>().ok()?, end.parse::<i32>().ok()?)));
+    match years {
+        Some((start, end)) if 1 <= start && start <= end && end <= 9999 => {
+            Ok((start..=end).collect())
+        }
+        _ => bail!(r#"Invalid year range "{range}""#),
+    }
+}
+
+/// 引数・日付の解析
+/// 受け取った日付(YYYY-MM-DD)を解析してNaiveDate型に変換して返す。
+///
+/// * `date` - 日付
+pub fn parse_date(date: String) -> Result<NaiveDate> {
+    match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+        Ok(date) => Ok(date),
+        _ => bail!(r#"Invalid date "{date}""#),
+    }
+}
+
+/// 引数・表記の揺れを含む日付の解析
+/// 受け取った日付(年・月・日を`-`、`/`、`.`で区切ったもの、月日は1桁も可)を解析してNaiveDate型に変換して返す。
+///
+/// * `date` - 日付
+pub fn parse_loose_date(date: String) -> Result<NaiveDate> {
+    let parts: Vec<&str> = date.trim().split(['-', '/', '.']).collect();
+    let numbers: Option<Vec<u32>> = parts
+        .iter()
+        .map(|part| {
+            if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                None
+            } else {
+                part.parse().ok()
+            }
+        })
+        .collect();
+    match numbers.as_deref() {
+        Some(&[year, month, day]) if parts[0].len() == 4 => {
+            match NaiveDate::from_ymd_opt(year as i32, month, day) {
+                Some(date) => Ok(date),
+                None => bail!(r#"Invalid date "{date}""#),
+            }
+        }
+        _ => bail!(r#"Invalid date "{date}""#),
+    }
+}
+
+/// 引数・日付範囲の解析
+/// 受け取った日付範囲(YYYY-MM-DD:YYYY-MM-DD)を解析し、範囲内(両端を含む)の日付を返す。
+///
+/// * `range` - 日付範囲
+pub fn parse_date_range(range: String) -> Result<Vec<NaiveDate>> {
+    let Some((start, end)) = range.split_once(':') else {
+        bail!(r#"Invalid date range "{range}""#);
+    };
+    let (start, end) = (parse_date(start.to_string())?, parse_date(end.to_string())?);
+    if end < start {
+        bail!(r#"Invalid date range "{range}""#);
+    }
+    Ok(start.iter_days().take_while(|date| *date <= end).collect())
+}
+
+/// 引数・曜日の解析
+/// 受け取った英語の曜日名(前方一致)を解析してWeekday型に変換して返す。
+///
+/// * `weekday` - 曜日名
+pub fn parse_weekday(weekday: String) -> Result<Weekday> {
+    parse_localized_weekday(weekday, &Locale::default())
+}
+
+/// 引数・曜日の解析(ロケール指定)
+/// 受け取ったロケールの曜日名(前方一致)を解析してWeekday型に変換して返す。
+///
+/// * `weekday` - 曜日名
+/// * `locale`  - 曜日名のロケール
+pub fn parse_localized_weekday(weekday: String, locale: &Locale) -> Result<Weekday> {
+    let lower = &weekday.to_lowercase();
+    let matches: Vec<_> = locale
+        .weekday_names()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            if name.to_lowercase().starts_with(lower) {
+                Some(i)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if matches.len() == 1 {
+        Ok(WEEKDAYS[matches[0]])
+    } else {
+        bail!(r#"Invalid weekday "{weekday}""#)
+    }
+}
+
 // ---------------------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
     use super::{
-        format_month, get_after_month, get_before_month, get_year_month, is_all_same_year,
-        last_day_in_month, parse_month,
+        count_business_days, count_weekday, days_in_month, first_of_month_offset,
+        format_agenda_line, format_days_between, format_iso_week, format_month,
+        format_months_between, format_ndjson_line, format_peek, format_summary, format_tsv_month,
+        format_week, format_weekday_count, format_weekend_count, format_year_progress,
+        get_after_month, get_before_month, get_year_month, is_all_same_year, is_highlighted,
+        is_leap_year, is_today, join_cells, last_day_in_month, leading_blanks, month_days,
+        pad_center, parse_date, parse_date_range, parse_localized_month, parse_localized_weekday,
+        parse_loose_date, parse_month, parse_weekday, parse_year_month, parse_year_range,
+        to_arabic_indic, to_roman, visible_width, week_rows, weekday_diff_days, weeks_since,
+        FormatOptions, HeaderAlign, WeekColumn, WeekRule, WEEKDAYS,
     };
-    use chrono::NaiveDate;
+    use crate::utils::locale_util::{parse_locale, Locale};
+    use crate::utils::theme_util::Theme;
+    use chrono::{Datelike, NaiveDate, Weekday};
+    use std::collections::HashSet;
 
     #[test]
     fn test_format_month() {
         let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
         let leap_february = vec![
-            "   February 2020      ",
-            "Su Mo Tu We Th Fr Sa  ",
-            "                   1  ",
-            " 2  3  4  5  6  7  8  ",
-            " 9 10 11 12 13 14 15  ",
-            "16 17 18 19 20 21 22  ",
-            "23 24 25 26 27 28 29  ",
-            "                      ",
+            "   February 2020    ",
+            "Su Mo Tu We Th Fr Sa",
+            "                   1",
+            " 2  3  4  5  6  7  8",
+            " 9 10 11 12 13 14 15",
+            "16 17 18 19 20 21 22",
+            "23 24 25 26 27 28 29",
+            "                    ",
         ];
-        assert_eq!(format_month(2020, 2, true, today), leap_february);
+        let opts = FormatOptions::default();
+        assert_eq!(format_month(2020, 2, true, today, &opts), leap_february);
 
         let may = vec![
-            "        May           ",
-            "Su Mo Tu We Th Fr Sa  ",
-            "                1  2  ",
-            " 3  4  5  6  7  8  9  ",
-            "10 11 12 13 14 15 16  ",
-            "17 18 19 20 21 22 23  ",
-            "24 25 26 27 28 29 30  ",
-            "31                    ",
+            "        May         ",
+            "Su Mo Tu We Th Fr Sa",
+            "                1  2",
+            " 3  4  5  6  7  8  9",
+            "10 11 12 13 14 15 16",
+            "17 18 19 20 21 22 23",
+            "24 25 26 27 28 29 30",
+            "31                  ",
         ];
-        assert_eq!(format_month(2020, 5, false, today), may);
+        assert_eq!(format_month(2020, 5, false, today, &opts), may);
 
         let april_hl = vec![
-            "     April 2021       ",
-            "Su Mo Tu We Th Fr Sa  ",
-            "             1  2  3  ",
-            " 4  5  6 \u{1b}[7m 7\u{1b}[0m  8  9 10  ",
-            "11 12 13 14 15 16 17  ",
-            "18 19 20 21 22 23 24  ",
-            "25 26 27 28 29 30     ",
-            "                      ",
+            "     April 2021     ",
+            "Su Mo Tu We Th Fr Sa",
+            "             1  2  3",
+            " 4  5  6 \u{1b}[7m 7\u{1b}[0m  8  9 10",
+            "11 12 13 14 15 16 17",
+            "18 19 20 21 22 23 24",
+            "25 26 27 28 29 30   ",
+            "                    ",
         ];
         let today = NaiveDate::from_ymd_opt(2021, 4, 7).unwrap();
-        assert_eq!(format_month(2021, 4, true, today), april_hl);
+        assert_eq!(format_month(2021, 4, true, today, &opts), april_hl);
+    }
+
+    #[test]
+    fn test_format_month_wide_weekdays() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            wide_weekdays: true,
+            ..Default::default()
+        };
+        let may = vec![
+            "         May 2020          ",
+            "Sun Mon Tue Wed Thu Fri Sat",
+            "                      1   2",
+            "  3   4   5   6   7   8   9",
+            " 10  11  12  13  14  15  16",
+            " 17  18  19  20  21  22  23",
+            " 24  25  26  27  28  29  30",
+            " 31                        ",
+        ];
+        assert_eq!(format_month(2020, 5, true, today, &opts), may);
+        assert_eq!(opts.line_width(), 27);
+    }
+
+    #[test]
+    fn test_format_month_highlights() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            highlights: HashSet::from([
+                NaiveDate::from_ymd_opt(2021, 4, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2021, 4, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2021, 5, 1).unwrap(),
+            ]),
+            ..Default::default()
+        };
+        let lines = format_month(2021, 4, true, today, &opts);
+        assert_eq!(lines[2], "            \u{1b}[7m 1\u{1b}[0m  2  3");
+        assert_eq!(lines[6], "25 26 27 28 29 \u{1b}[7m30\u{1b}[0m   ");
+    }
+
+    #[test]
+    fn test_format_month_recur() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let opts = FormatOptions {
+            recur: HashSet::from([Weekday::Mon, Weekday::Fri]),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        // 当日の装飾が優先される
+        assert_eq!(
+            lines[3],
+            " 3 \u{1b}[7m 4\u{1b}[0m  5  6  7 \u{1b}[1m 8\u{1b}[0m  9"
+        );
+        assert_eq!(
+            lines[4],
+            "10 \u{1b}[1m11\u{1b}[0m 12 13 14 \u{1b}[1m15\u{1b}[0m 16"
+        );
     }
 
     #[test]
@@ -300,6 +1544,153 @@ mod tests {
             last_day_in_month(2020, 4),
             NaiveDate::from_ymd_opt(2020, 4, 30).unwrap()
         );
+        assert_eq!(
+            last_day_in_month(9999, 12),
+            NaiveDate::from_ymd_opt(9999, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2024, 4), 30);
+        assert_eq!(days_in_month(2024, 12), 31);
+        assert_eq!(days_in_month(9999, 12), 31);
+        assert_eq!(month_days(9999, 12).len(), 31);
+    }
+
+    #[test]
+    fn test_is_today() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        assert!(is_today(today, today));
+        assert!(!is_today(today.succ_opt().unwrap(), today));
+    }
+
+    #[test]
+    fn test_is_highlighted() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let highlights = HashSet::from([date]);
+        assert!(is_highlighted(date, &highlights));
+        assert!(!is_highlighted(date.succ_opt().unwrap(), &highlights));
+        assert!(!is_highlighted(date, &HashSet::new()));
+    }
+
+    #[test]
+    fn test_format_days_between() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(
+            format_days_between(start, end, &FormatOptions::default()),
+            "2024-01-01 to 2024-12-31: 366 days inclusive, 365 days exclusive"
+        );
+        assert_eq!(
+            format_days_between(end, start, &FormatOptions::default()),
+            "2024-01-01 to 2024-12-31: 366 days inclusive, 365 days exclusive (reversed)"
+        );
+        assert_eq!(
+            format_days_between(start, start, &FormatOptions::default()),
+            "2024-01-01 to 2024-01-01: 1 day inclusive, 0 days exclusive"
+        );
+        assert_eq!(
+            format_days_between(
+                start,
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                &FormatOptions::default()
+            ),
+            "2024-01-01 to 2024-01-02: 2 days inclusive, 1 day exclusive"
+        );
+    }
+
+    #[test]
+    fn test_format_months_between() {
+        assert_eq!(
+            format_months_between((2024, 1), (2024, 12), &FormatOptions::default()),
+            "2024-01 to 2024-12: 12 months"
+        );
+        assert_eq!(
+            format_months_between((2024, 3), (2024, 3), &FormatOptions::default()),
+            "2024-03 to 2024-03: 1 month"
+        );
+        assert_eq!(
+            format_months_between((2025, 2), (2023, 11), &FormatOptions::default()),
+            "2023-11 to 2025-02: 16 months (reversed)"
+        );
+        let opts = FormatOptions {
+            zero_pad_year: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_months_between((44, 1), (44, 3), &opts),
+            "0044-01 to 0044-03: 3 months"
+        );
+    }
+
+    #[test]
+    fn test_format_between_locale_number() {
+        let opts = FormatOptions {
+            locale: parse_locale("de").unwrap(),
+            locale_number: true,
+            ..Default::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        assert_eq!(
+            format_days_between(start, end, &opts),
+            "2020-01-01 to 2024-06-01: 1.614 days inclusive, 1.613 days exclusive"
+        );
+        assert_eq!(
+            format_months_between((1, 1), (1200, 6), &opts),
+            "1-01 to 1200-06: 14.394 months"
+        );
+    }
+
+    #[test]
+    fn test_format_year_progress() {
+        assert_eq!(
+            format_year_progress(
+                NaiveDate::from_ymd_opt(2024, 4, 7).unwrap(),
+                &FormatOptions::default()
+            ),
+            "Day 98 of 366 (26.8%)"
+        );
+        assert_eq!(
+            format_year_progress(
+                NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+                &FormatOptions::default()
+            ),
+            "Day 365 of 365 (100.0%)"
+        );
+        // 数値をロケールの慣習でフォーマットする場合(「--locale-number」)
+        let opts = FormatOptions {
+            locale: parse_locale("de").unwrap(),
+            locale_number: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_year_progress(NaiveDate::from_ymd_opt(2024, 4, 7).unwrap(), &opts),
+            "Day 98 of 366 (26,8%)"
+        );
+    }
+
+    #[test]
+    fn test_is_leap_year() {
+        assert!(is_leap_year(2020));
+        assert!(!is_leap_year(2021));
+        assert!(!is_leap_year(1900));
+        assert!(is_leap_year(2000));
+    }
+
+    #[test]
+    fn test_first_of_month_offset() {
+        assert_eq!(first_of_month_offset(2024, 6, 0), (2024, 6));
+        assert_eq!(first_of_month_offset(2024, 6, 6), (2024, 12));
+        assert_eq!(first_of_month_offset(2024, 6, 7), (2025, 1));
+        assert_eq!(first_of_month_offset(2024, 11, 14), (2026, 1));
+        assert_eq!(first_of_month_offset(2024, 6, -5), (2024, 1));
+        assert_eq!(first_of_month_offset(2024, 6, -6), (2023, 12));
+        assert_eq!(first_of_month_offset(2024, 1, -1), (2023, 12));
+        assert_eq!(first_of_month_offset(2024, 2, -26), (2021, 12));
     }
 
     #[test]
@@ -328,6 +1719,10 @@ mod tests {
 
     #[test]
     fn test_get_after_month() {
+        assert_eq!(
+            get_after_month(0, 9999, 12),
+            NaiveDate::from_ymd_opt(9999, 12, 31).unwrap()
+        );
         assert_eq!(
             get_after_month(0, 2022, 6),
             NaiveDate::from_ymd_opt(2022, 6, 30).unwrap()
@@ -385,6 +1780,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_agenda_line() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        assert_eq!(format_agenda_line(date, false), "Mon 2024-03-04");
+        assert_eq!(format_agenda_line(date, true), "Mon 2024-03-04 *");
+    }
+
+    #[test]
+    fn test_format_ndjson_line() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        assert_eq!(
+            format_ndjson_line(date, NaiveDate::from_ymd_opt(2024, 3, 5).unwrap()),
+            r#"{"date":"2024-03-04","weekday":"Mon","today":false,"day_of_year":64,"iso_week":10}"#
+        );
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(
+            format_ndjson_line(date, date),
+            r#"{"date":"2024-12-31","weekday":"Tue","today":true,"day_of_year":366,"iso_week":1}"#
+        );
+    }
+
+    #[test]
+    fn test_weekday_diff_days() {
+        // 2023年と2024年の3月は曜日が1つずれる
+        assert_eq!(weekday_diff_days(3, 2023, 2024).len(), 31);
+        // 2019年と2024年の4月は曜日が一致する
+        assert!(weekday_diff_days(4, 2019, 2024).is_empty());
+        // 2020年2月29日は2021年に存在しない
+        assert_eq!(
+            weekday_diff_days(2, 2020, 2021)
+                .into_iter()
+                .filter(|date| date.day() == 29)
+                .count(),
+            1
+        );
+        // 2020年と2025年の2月は28日まで曜日が一致する
+        assert_eq!(
+            weekday_diff_days(2, 2020, 2025),
+            HashSet::from([NaiveDate::from_ymd_opt(2020, 2, 29).unwrap()])
+        );
+    }
+
     #[test]
     fn test_is_all_same_year() {
         assert!(is_all_same_year(vec![(2024, 6), (2024, 7), (2024, 8)]));
@@ -407,6 +1844,14 @@ mod tests {
 
     #[test]
     fn test_parse_month() {
+        let res = parse_month("".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), "empty month name");
+
+        let res = parse_month("   ".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), "empty month name");
+
         let res = parse_month("1".to_string());
         assert!(res.is_ok());
         assert_eq!(res.unwrap(), 1u32);
@@ -437,4 +1882,988 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().to_string(), r#"Invalid month "foo""#);
     }
+
+    #[test]
+    fn test_parse_localized_weekday() {
+        let locale = parse_locale("es").unwrap();
+        let res = parse_localized_weekday("mié".to_string(), &locale);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Weekday::Wed);
+
+        let res = parse_localized_weekday("m".to_string(), &locale);
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid weekday "m""#);
+    }
+
+    #[test]
+    fn test_month_days() {
+        let days = month_days(2020, 2);
+        assert_eq!(days.len(), 29);
+        assert_eq!(days[0], NaiveDate::from_ymd_opt(2020, 2, 1).unwrap());
+        assert_eq!(days[28], NaiveDate::from_ymd_opt(2020, 2, 29).unwrap());
+        assert_eq!(month_days(2021, 2).len(), 28);
+        assert_eq!(month_days(2024, 12).len(), 31);
+    }
+
+    #[test]
+    fn test_count_weekday() {
+        assert_eq!(count_weekday(2024, 3, Weekday::Fri), 5);
+        assert_eq!(count_weekday(2024, 3, Weekday::Mon), 4);
+        assert_eq!(count_weekday(2021, 2, Weekday::Sun), 4);
+        assert_eq!(
+            format_weekday_count(2024, 3, Weekday::Fri),
+            "Fridays in March 2024: 5"
+        );
+    }
+
+    #[test]
+    fn test_format_weekend_count() {
+        assert_eq!(
+            format_weekend_count(2024, 3),
+            "Weekend days in March 2024: 10"
+        );
+        assert_eq!(
+            format_weekend_count(2021, 2),
+            "Weekend days in February 2021: 8"
+        );
+    }
+
+    #[test]
+    fn test_parse_localized_month() {
+        let locale = parse_locale("de").unwrap();
+        let res = parse_localized_month("mär".to_string(), &locale, false);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), 3u32);
+
+        let res = parse_localized_month("Dezember".to_string(), &locale, false);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), 12u32);
+
+        let res = parse_localized_month("ju".to_string(), &locale, false);
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid month "ju""#);
+    }
+
+    #[test]
+    fn test_parse_localized_month_strict() {
+        let locale = Locale::default();
+        let res = parse_localized_month("f".to_string(), &locale, true);
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid month "f""#);
+
+        let res = parse_localized_month("February".to_string(), &locale, true);
+        assert_eq!(res.unwrap(), 2u32);
+
+        let res = parse_localized_month("2".to_string(), &locale, true);
+        assert_eq!(res.unwrap(), 2u32);
+    }
+
+    #[test]
+    fn test_format_month_locale() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            locale: parse_locale("fr").unwrap(),
+            ..Default::default()
+        };
+        let lines = format_month(2020, 8, true, today, &opts);
+        assert_eq!(lines[0], "     août 2020      ");
+        assert_eq!(lines[1], "di lu ma me je ve sa");
+    }
+
+    #[test]
+    fn test_leading_blanks() {
+        // 月初の曜日が日曜日〜土曜日となる月
+        let months = [
+            (2024, 9),  // 日曜日
+            (2024, 7),  // 月曜日
+            (2024, 10), // 火曜日
+            (2024, 5),  // 水曜日
+            (2024, 2),  // 木曜日
+            (2024, 3),  // 金曜日
+            (2024, 6),  // 土曜日
+        ];
+        for (i, &first_day_of_week) in WEEKDAYS.iter().enumerate() {
+            for (j, &(year, month)) in months.iter().enumerate() {
+                let month_first_weekday =
+                    NaiveDate::from_ymd_opt(year, month, 1).unwrap().weekday();
+                assert_eq!(month_first_weekday, WEEKDAYS[j]);
+                assert_eq!(
+                    leading_blanks(first_day_of_week, month_first_weekday),
+                    (j + 7 - i) % 7
+                );
+            }
+        }
+        assert_eq!(leading_blanks(Weekday::Sun, Weekday::Sat), 6);
+        assert_eq!(leading_blanks(Weekday::Mon, Weekday::Sun), 6);
+        assert_eq!(leading_blanks(Weekday::Sat, Weekday::Sat), 0);
+        assert_eq!(leading_blanks(Weekday::Sat, Weekday::Fri), 6);
+    }
+
+    #[test]
+    fn test_week_rows() {
+        // 2026年2月は日曜日始まりの28日間
+        assert_eq!(week_rows(2026, 2, Weekday::Sun), 4);
+        assert_eq!(week_rows(2026, 2, Weekday::Mon), 5);
+        // 2024年3月は金曜日始まりの31日間
+        assert_eq!(week_rows(2024, 3, Weekday::Sun), 6);
+        assert_eq!(week_rows(2024, 3, Weekday::Mon), 5);
+        assert_eq!(week_rows(2024, 3, Weekday::Fri), 5);
+    }
+
+    #[test]
+    fn test_format_month_saturday() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            first_day: Weekday::Sat,
+            ..Default::default()
+        };
+        // 2024年6月1日は土曜日のため先頭の空白はない
+        let june = vec![
+            "     June 2024      ",
+            "Sa Su Mo Tu We Th Fr",
+            " 1  2  3  4  5  6  7",
+            " 8  9 10 11 12 13 14",
+            "15 16 17 18 19 20 21",
+            "22 23 24 25 26 27 28",
+            "29 30               ",
+            "                    ",
+        ];
+        assert_eq!(format_month(2024, 6, true, today, &opts), june);
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[2], "                   1");
+    }
+
+    #[test]
+    fn test_format_month_first_day() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            ..Default::default()
+        };
+        let september = vec![
+            "   September 2024   ",
+            "Mo Tu We Th Fr Sa Su",
+            "                   1",
+            " 2  3  4  5  6  7  8",
+            " 9 10 11 12 13 14 15",
+            "16 17 18 19 20 21 22",
+            "23 24 25 26 27 28 29",
+            "30                  ",
+        ];
+        assert_eq!(format_month(2024, 9, true, today, &opts), september);
+
+        let opts = FormatOptions {
+            first_day: Weekday::Sat,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 9, true, today, &opts);
+        assert_eq!(lines[1], "Sa Su Mo Tu We Th Fr");
+        assert_eq!(lines[2], "    1  2  3  4  5  6");
+    }
+
+    #[test]
+    fn test_parse_weekday() {
+        let res = parse_weekday("fri".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Weekday::Fri);
+
+        let res = parse_weekday("Sunday".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Weekday::Sun);
+
+        let res = parse_weekday("sa".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Weekday::Sat);
+
+        let res = parse_weekday("t".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid weekday "t""#);
+    }
+
+    #[test]
+    fn test_count_business_days() {
+        assert_eq!(count_business_days(&[(2024, 3)], &HashSet::new()), 21);
+        let holidays = HashSet::from([
+            NaiveDate::from_ymd_opt(2024, 3, 20).unwrap(),
+            // 週末の休日は差し引かない
+            NaiveDate::from_ymd_opt(2024, 3, 23).unwrap(),
+        ]);
+        assert_eq!(count_business_days(&[(2024, 3)], &holidays), 20);
+        assert_eq!(
+            count_business_days(&[(2024, 3), (2024, 4)], &HashSet::new()),
+            43
+        );
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let start_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let end_date = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        assert_eq!(
+            format_summary(start_date, end_date, &FormatOptions::default()),
+            "92 days (2024-03-01 to 2024-05-31)"
+        );
+        assert_eq!(
+            format_summary(start_date, start_date, &FormatOptions::default()),
+            "1 days (2024-03-01 to 2024-03-01)"
+        );
+    }
+
+    #[test]
+    fn test_parse_year_month() {
+        let res = parse_year_month("2024-03".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), (2024, 3));
+
+        let res = parse_year_month("1900-12".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), (1900, 12));
+
+        let res = parse_year_month("2024-13".to_string());
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            r#"Invalid year-month "2024-13""#
+        );
+
+        let res = parse_year_month("2024".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid year-month "2024""#);
+    }
+
+    #[test]
+    fn test_format_iso_week() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 26).unwrap();
+        assert_eq!(format_iso_week(date), "2024-W09-1");
+        let date = NaiveDate::from_ymd_opt(2024, 3, 3).unwrap();
+        assert_eq!(format_iso_week(date), "2024-W09-7");
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(format_iso_week(date), "2025-W01-2");
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        assert_eq!(format_iso_week(date), "2020-W53-5");
+    }
+
+    #[test]
+    fn test_format_month_japanese_era() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            japanese_era: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_month(2019, 5, true, today, &opts)[0],
+            "    May 令和元年    "
+        );
+        assert_eq!(
+            format_month(2019, 4, true, today, &opts)[0],
+            "   April 平成31年   "
+        );
+        assert_eq!(
+            format_month(2019, 4, false, today, &opts)[0],
+            "       April        "
+        );
+    }
+
+    #[test]
+    fn test_format_month_debug_column() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            week_column: Some(WeekColumn::DebugOffset),
+            ..Default::default()
+        };
+        let march = vec![
+            "        March 2024     ",
+            "   Su Mo Tu We Th Fr Sa",
+            " 5                 1  2",
+            " 0  3  4  5  6  7  8  9",
+            " 0 10 11 12 13 14 15 16",
+            " 0 17 18 19 20 21 22 23",
+            " 0 24 25 26 27 28 29 30",
+            " 0 31                  ",
+        ];
+        assert_eq!(format_month(2024, 3, true, today, &opts), march);
+        assert_eq!(opts.line_width(), 23);
+
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            ..opts
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[2], " 5              1  2  3");
+        assert_eq!(lines[3], " 1  4  5  6  7  8  9 10");
+    }
+
+    #[test]
+    fn test_format_month_week_of_month_column() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            week_column: Some(WeekColumn::WeekOfMonth),
+            ..Default::default()
+        };
+        // 2024年6月は土曜日始まりで6行
+        let lines = format_month(2024, 6, true, today, &opts);
+        assert_eq!(lines[1], "   Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[2], " 1                    1");
+        assert_eq!(lines[3], " 2  2  3  4  5  6  7  8");
+        assert_eq!(lines[7], " 6 30                  ");
+    }
+
+    #[test]
+    fn test_format_month_iso_week_column() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            week_column: Some(WeekColumn::WeekNumber(WeekRule::Iso)),
+            ..Default::default()
+        };
+        // 2021年1月1日〜3日は前年の第53週
+        let lines = format_month(2021, 1, true, today, &opts);
+        assert_eq!(lines[1], "   Mo Tu We Th Fr Sa Su");
+        assert_eq!(lines[2], "53              1  2  3");
+        assert_eq!(lines[3], " 1  4  5  6  7  8  9 10");
+        assert_eq!(lines[6], " 4 25 26 27 28 29 30 31");
+    }
+
+    #[test]
+    fn test_week_rule() {
+        // 2022年1月1日は土曜日(日曜日始まり)
+        let jan1 = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+        let jan2 = NaiveDate::from_ymd_opt(2022, 1, 2).unwrap();
+        let sun = Weekday::Sun;
+        assert_eq!(WeekRule::Iso.week_number(jan1, sun), 52);
+        assert_eq!(WeekRule::Jan1.week_number(jan1, sun), 1);
+        assert_eq!(WeekRule::FirstFull.week_number(jan1, sun), 52);
+        // 1月2日〜8日の週は3つの数え方で全て異なる
+        assert_eq!(WeekRule::Iso.week_number(jan2, sun), 52);
+        assert_eq!(WeekRule::Jan1.week_number(jan2, sun), 2);
+        assert_eq!(WeekRule::FirstFull.week_number(jan2, sun), 1);
+        // 2024年12月29日〜2025年1月4日の週は1月1日を含むため翌年の第1週
+        let dec29 = NaiveDate::from_ymd_opt(2024, 12, 29).unwrap();
+        assert_eq!(WeekRule::Jan1.week_number(dec29, sun), 1);
+        assert_eq!(WeekRule::FirstFull.week_number(dec29, sun), 52);
+    }
+
+    #[test]
+    fn test_weeks_since() {
+        let epoch = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        // 日曜日始まりでは2023年12月31日〜2024年1月6日が第0週
+        let date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        assert_eq!(weeks_since(epoch, date, Weekday::Sun), 0);
+        assert_eq!(weeks_since(epoch, date, Weekday::Mon), -1);
+        let date = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        assert_eq!(weeks_since(epoch, date, Weekday::Sun), 13);
+    }
+
+    #[test]
+    fn test_format_month_weeks_since_column() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            week_column: Some(WeekColumn::WeeksSince {
+                epoch: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                digits: 3,
+            }),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 4, true, today, &opts);
+        assert_eq!(lines[1], "    Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[2], "222     1  2  3  4  5  6");
+        assert_eq!(lines[6], "226 28 29 30            ");
+    }
+
+    #[test]
+    fn test_format_month_week_rule_column() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            week_column: Some(WeekColumn::WeekNumber(WeekRule::Jan1)),
+            ..Default::default()
+        };
+        let lines = format_month(2022, 1, true, today, &opts);
+        assert_eq!(lines[1], "   Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[2], " 1                    1");
+        assert_eq!(lines[3], " 2  2  3  4  5  6  7  8");
+        assert_eq!(lines[7], " 6 30 31               ");
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("May"), 3);
+        assert_eq!(visible_width("令和元年"), 8);
+        assert_eq!(visible_width("\u{1b}[7m 7\u{1b}[0m  8"), 5);
+        assert_eq!(visible_width("\u{1b}[1;38;5;61mMay\u{1b}[0m"), 3);
+        assert_eq!(
+            visible_width(" \u{1b}]8;;https://example.com/\u{1b}\\7\u{1b}]8;;\u{1b}\\  8"),
+            5
+        );
+    }
+
+    #[test]
+    fn test_format_month_theme() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let opts = FormatOptions {
+            theme: Theme::solarized(),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[0], "     \u{1b}[1;38;5;61mMarch 2024\u{1b}[0m     ");
+        assert_eq!(
+            lines[3],
+            "\u{1b}[38;5;166m 3\u{1b}[0m \u{1b}[48;5;33;38;5;230m 4\u{1b}[0m  5  6  7  8 \u{1b}[38;5;166m 9\u{1b}[0m"
+        );
+
+        let opts = FormatOptions {
+            theme: Theme::plain(),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[3], " 3  4  5  6  7  8  9");
+    }
+
+    #[test]
+    fn test_format_month_no_weekday_header() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            no_weekday_header: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "     March 2024     ");
+        assert_eq!(lines[1], "                1  2");
+        assert_eq!(lines[6], "31                  ");
+    }
+
+    #[test]
+    fn test_header_align() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        for (header_align, expected) in [
+            (HeaderAlign::Left, "March 2024          "),
+            (HeaderAlign::Center, "     March 2024     "),
+            (HeaderAlign::Right, "          March 2024"),
+        ] {
+            let opts = FormatOptions {
+                header_align,
+                ..Default::default()
+            };
+            let lines = format_month(2024, 3, true, today, &opts);
+            assert_eq!(lines[0], expected);
+            assert_eq!(lines[0].len(), lines[1].len());
+        }
+        assert_eq!(HeaderAlign::Right.pad("令和元年", 10), "  令和元年");
+    }
+
+    #[test]
+    fn test_pad_center() {
+        assert_eq!(pad_center("May", 8), "  May   ");
+        assert_eq!(pad_center("令和元年", 10), " 令和元年 ");
+        assert_eq!(pad_center("too long", 4), "too long");
+    }
+
+    #[test]
+    fn test_format_week() {
+        // 月をまたぐ週(2024-04-30は火曜日)
+        let today = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let opts = FormatOptions::default();
+        assert_eq!(
+            format_week(today, &opts),
+            vec![
+                "Su Mo Tu We Th Fr Sa",
+                "28 29 \u{1b}[7m30\u{1b}[0m  1  2  3  4"
+            ]
+        );
+
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            ..Default::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2024, 5, 5).unwrap();
+        assert_eq!(
+            format_week(today, &opts),
+            vec![
+                "Mo Tu We Th Fr Sa Su",
+                "29 30  1  2  3  4 \u{1b}[7m 5\u{1b}[0m"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_peek() {
+        // 月初の週(2024-03-01は金曜日)は前月の日付を含まない
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let opts = FormatOptions::default();
+        assert_eq!(
+            format_peek(today, &opts),
+            vec![
+                "Su Mo Tu We Th Fr Sa",
+                "               \u{1b}[7m 1\u{1b}[0m  2"
+            ]
+        );
+
+        // 月末の週(2024-03-31は日曜日)
+        let today = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        assert_eq!(
+            format_peek(today, &opts),
+            vec!["Su Mo Tu We Th Fr Sa", "\u{1b}[7m31\u{1b}[0m"]
+        );
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_peek(today, &opts),
+            vec![
+                "Mo Tu We Th Fr Sa Su",
+                "25 26 27 28 29 30 \u{1b}[7m31\u{1b}[0m"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_month_title_below() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            title_below: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 2, true, today, &opts);
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[5], "25 26 27 28 29      ");
+        assert_eq!(lines[7], "   February 2024    ");
+    }
+
+    #[test]
+    fn test_format_month_link_format() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            link_format: Some(String::from("https://example.com/{date}")),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert!(lines[3]
+            .starts_with(" \u{1b}]8;;https://example.com/2024-03-03\u{1b}\\3\u{1b}]8;;\u{1b}\\ "));
+        assert!(lines[4]
+            .contains(" \u{1b}]8;;https://example.com/2024-03-12\u{1b}\\12\u{1b}]8;;\u{1b}\\ "));
+        // エスケープシーケンスを除いた行の幅は変わらない
+        for line in &lines {
+            assert_eq!(visible_width(line), 20);
+        }
+    }
+
+    #[test]
+    fn test_format_month_from_day() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let opts = FormatOptions {
+            from_day: Some(15),
+            ..Default::default()
+        };
+        let march = vec![
+            "     March 2024     ",
+            "Su Mo Tu We Th Fr Sa",
+            "                    ",
+            "                    ",
+            "               15 16",
+            "17 18 19 \u{1b}[7m20\u{1b}[0m 21 22 23",
+            "24 25 26 27 28 29 30",
+            "31                  ",
+        ];
+        assert_eq!(format_month(2024, 3, true, today, &opts), march);
+    }
+
+    #[test]
+    fn test_to_roman() {
+        let months: Vec<String> = (1..=12).map(to_roman).collect();
+        assert_eq!(
+            months,
+            ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII"]
+        );
+        assert_eq!(to_roman(2024), "MMXXIV");
+    }
+
+    #[test]
+    fn test_format_month_roman() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            roman_months: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 4, true, today, &opts);
+        assert_eq!(lines[0], "      IV 2024       ");
+        let lines = format_month(2024, 4, false, today, &opts);
+        assert_eq!(lines[0], "         IV         ");
+    }
+
+    #[test]
+    fn test_to_arabic_indic() {
+        assert_eq!(to_arabic_indic(7), "٧");
+        assert_eq!(to_arabic_indic(2024), "٢٠٢٤");
+    }
+
+    #[test]
+    fn test_format_week_arabic_digits() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            arabic_digits: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[3], " ٣  ٤  ٥  ٦  ٧  ٨  ٩");
+        assert_eq!(lines[4], "١٠ ١١ ١٢ ١٣ ١٤ ١٥ ١٦");
+        assert_eq!(visible_width(&lines[4]), 20);
+    }
+
+    #[test]
+    fn test_format_month_collapse_weekends() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 8).unwrap();
+        let opts = FormatOptions {
+            collapse_weekends: true,
+            ..Default::default()
+        };
+        // 2024年6月1日は土曜日のため、週末のみの最初の行は出力しない
+        let june = vec![
+            "  June 2024   ",
+            "Mo Tu We Th Fr",
+            " 3  4  5  6  7",
+            "10 11 12 13 14",
+            "17 18 19 20 21",
+            "24 25 26 27 28",
+            "              ",
+            "              ",
+        ];
+        assert_eq!(format_month(2024, 6, true, today, &opts), june);
+    }
+
+    #[test]
+    fn test_format_month_header_gap() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            header_gap: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa");
+        assert_eq!(lines[2], "                    ");
+        assert_eq!(lines[3], "                1  2");
+        // 6週ある月も同じ行数
+        assert_eq!(format_month(2024, 6, true, today, &opts).len(), 9);
+    }
+
+    #[test]
+    fn test_format_month_min_width() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            locale: parse_locale("es").unwrap(),
+            min_width: 24,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 9, true, today, &opts);
+        assert_eq!(lines[0], "    septiembre 2024     ");
+        assert_eq!(lines[1], "do lu ma mi ju vi sá    ");
+        assert_eq!(lines[2], " 1  2  3  4  5  6  7    ");
+        for line in &lines {
+            assert_eq!(visible_width(line), 24);
+        }
+
+        // 最小の幅より広い場合は変わらない
+        let opts = FormatOptions {
+            min_width: 10,
+            ..Default::default()
+        };
+        assert_eq!(opts.line_width(), 20);
+    }
+
+    #[test]
+    fn test_format_tsv_month() {
+        let opts = FormatOptions::default();
+        let lines = format_tsv_month(2024, 3, &opts);
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "year\tmonth\tSu\tMo\tTu\tWe\tTh\tFr\tSa");
+        assert_eq!(lines[1], "2024\t3\t\t\t\t\t\t1\t2");
+        assert_eq!(lines[2], "2024\t3\t3\t4\t5\t6\t7\t8\t9");
+        assert_eq!(lines[6], "2024\t3\t31\t\t\t\t\t\t");
+
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            ..Default::default()
+        };
+        let lines = format_tsv_month(2024, 3, &opts);
+        assert_eq!(lines[0], "year\tmonth\tMo\tTu\tWe\tTh\tFr\tSa\tSu");
+        assert_eq!(lines[1], "2024\t3\t\t\t\t\t1\t2\t3");
+    }
+
+    #[test]
+    fn test_format_header_year() {
+        let opts = FormatOptions::default();
+        assert_eq!(opts.format_header_year(2024), "2024");
+        assert_eq!(opts.format_header_year(0), "1 BC");
+        assert_eq!(opts.format_header_year(-1), "2 BC");
+        let opts = FormatOptions {
+            astronomical_year: true,
+            ..Default::default()
+        };
+        assert_eq!(opts.format_header_year(0), "0");
+        assert_eq!(opts.format_header_year(-1), "-1");
+        let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert_eq!(
+            format_month(0, 3, true, today, &FormatOptions::default())[0],
+            "     March 1 BC     "
+        );
+    }
+
+    #[test]
+    fn test_format_year_zero_pad() {
+        let opts = FormatOptions {
+            zero_pad_year: true,
+            ..Default::default()
+        };
+        assert_eq!(opts.format_year(44), "0044");
+        assert_eq!(opts.format_year(2024), "2024");
+        assert_eq!(FormatOptions::default().format_year(44), "44");
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        assert_eq!(
+            format_month(44, 3, true, today, &opts)[0],
+            "     March 0044     "
+        );
+        assert_eq!(
+            format_tsv_month(44, 3, &opts)[1],
+            "0044\t3\t\t\t1\t2\t3\t4\t5"
+        );
+    }
+
+    #[test]
+    fn test_format_month_moon() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            moon: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 4, true, today, &opts);
+        assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa   ");
+        // 4月7日〜13日の週の中日(4月10日)は新月の直後
+        assert_eq!(lines[3], " 7  8  9 10 11 12 13 🌑");
+        assert_eq!(lines[5], "21 22 23 24 25 26 27 🌕");
+        assert!(lines.iter().all(|line| visible_width(line) == 23));
+    }
+
+    #[test]
+    fn test_format_month_day_separator() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 8).unwrap();
+        let opts = FormatOptions {
+            day_separator: String::from("·"),
+            today_brackets: true,
+            theme: Theme::plain(),
+            ..Default::default()
+        };
+        let march = vec![
+            "     March 2024     ",
+            "Su Mo Tu We Th Fr Sa",
+            "                1· 2",
+            " 3· 4· 5· 6· 7[ 8] 9",
+            "10·11·12·13·14·15·16",
+            "17·18·19·20·21·22·23",
+            "24·25·26·27·28·29·30",
+            "31                  ",
+        ];
+        assert_eq!(format_month(2024, 3, true, today, &opts), march);
+
+        let opts = FormatOptions {
+            day_separator: String::from("  "),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[1], "Su  Mo  Tu  We  Th  Fr  Sa");
+        assert_eq!(lines[2], "                     1   2");
+        assert!(lines.iter().all(|line| visible_width(line) == 26));
+    }
+
+    #[test]
+    fn test_format_month_continuous_days() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let opts = FormatOptions {
+            continuous_days: Some((start, NaiveDate::from_ymd_opt(2024, 4, 30).unwrap())),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 4, false, today, &opts);
+        assert_eq!(lines[2], "   32 33 34 35 36 37");
+        assert_eq!(lines[6], "59 60 61            ");
+
+        // 100日以上の期間は3桁幅のセルにする
+        let opts = FormatOptions {
+            continuous_days: Some((start, NaiveDate::from_ymd_opt(2024, 6, 30).unwrap())),
+            ..Default::default()
+        };
+        assert_eq!(opts.cell_width(), 3);
+        let lines = format_month(2024, 6, false, today, &opts);
+        assert_eq!(lines[1], "Sun Mon Tue Wed Thu Fri Sat");
+        assert_eq!(lines[2], "                         93");
+    }
+
+    #[test]
+    fn test_format_month_mark_boundaries() {
+        let today = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let opts = FormatOptions {
+            mark_boundaries: true,
+            ..Default::default()
+        };
+        let lines = format_month(2024, 4, true, today, &opts);
+        assert_eq!(lines[2], "   \u{1b}[4m 1\u{1b}[0m  2  3  4  5  6");
+        assert_eq!(lines[3], " 7  8  9 10 11 12 13");
+        // 当日は初日・最終日の装飾より優先
+        assert_eq!(lines[6], "28 29 \u{1b}[7m30\u{1b}[0m            ");
+
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(lines[2], "               \u{1b}[4m 1\u{1b}[0m  2");
+        assert_eq!(lines[7], "\u{1b}[4m31\u{1b}[0m                  ");
+    }
+
+    #[test]
+    fn test_join_cells() {
+        let opts = FormatOptions {
+            today_brackets: true,
+            ..Default::default()
+        };
+        let cells: Vec<String> = (10..=16).map(|day| format!("{day:>2}")).collect();
+        assert_eq!(join_cells(&cells, None, &opts), "10 11 12 13 14 15 16");
+        assert_eq!(join_cells(&cells, Some(3), &opts), "10 11 12[13]14 15 16");
+        // 行の端の2桁の日付は外側の括弧を省く
+        assert_eq!(join_cells(&cells, Some(0), &opts), "10]11 12 13 14 15 16");
+        assert_eq!(join_cells(&cells, Some(6), &opts), "10 11 12 13 14 15[16]");
+
+        // 行の端の1桁の日付は空白埋めを括弧に使う
+        let cells: Vec<String> = (3..=9).map(|day| format!("{day:>2}")).collect();
+        assert_eq!(join_cells(&cells, Some(0), &opts), "[3] 4  5  6  7  8  9");
+        assert_eq!(join_cells(&cells, Some(6), &opts), " 3  4  5  6  7  8[9]");
+
+        // 週の途中で終わる行は後ろに閉じ括弧を置く
+        let cells: Vec<String> = (29..=31).map(|day| format!("{day:>2}")).collect();
+        assert_eq!(join_cells(&cells, Some(2), &opts), "29 30[31]");
+
+        let opts = FormatOptions::default();
+        assert_eq!(join_cells(&cells, Some(2), &opts), "29 30 31");
+    }
+
+    #[test]
+    fn test_parse_year_range() {
+        let res = parse_year_range("2023-2025".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), vec![2023, 2024, 2025]);
+
+        let res = parse_year_range("2024-2024".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), vec![2024]);
+
+        for range in ["2025-2023", "0-2024", "2024-10000", "2024", "foo-bar"] {
+            let res = parse_year_range(range.to_string());
+            assert!(res.is_err());
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!(r#"Invalid year range "{range}""#)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let res = parse_date("2024-03-04".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+
+        let res = parse_date("2024-02-30".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid date "2024-02-30""#);
+
+        let res = parse_date("foo".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid date "foo""#);
+    }
+
+    #[test]
+    fn test_parse_loose_date() {
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        for date in [
+            "2024-03-04",
+            "2024-3-4",
+            "2024/3/04",
+            "2024.03.4",
+            " 2024-3-4 ",
+        ] {
+            let res = parse_loose_date(date.to_string());
+            assert!(res.is_ok());
+            assert_eq!(res.unwrap(), expected);
+        }
+
+        for date in [
+            "2024-02-30",
+            "24-3-4",
+            "2024-3",
+            "2024-3-4-5",
+            "2024-+3-4",
+            "foo",
+        ] {
+            let res = parse_loose_date(date.to_string());
+            assert!(res.is_err());
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!(r#"Invalid date "{date}""#)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_date_range() {
+        let res = parse_date_range("2024-03-30:2024-04-02".to_string());
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            vec![
+                NaiveDate::from_ymd_opt(2024, 3, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 4, 2).unwrap(),
+            ]
+        );
+
+        let res = parse_date_range("2024-03-10:2024-03-10".to_string());
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().len(), 1);
+
+        let res = parse_date_range("2024-03-17:2024-03-10".to_string());
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            r#"Invalid date range "2024-03-17:2024-03-10""#
+        );
+
+        let res = parse_date_range("2024-03-10".to_string());
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            r#"Invalid date range "2024-03-10""#
+        );
+
+        let res = parse_date_range("2024-03-10:foo".to_string());
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid date "foo""#);
+    }
+
+    #[test]
+    fn test_format_month_highlight_range() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            highlights: parse_date_range("2024-03-30:2024-04-02".to_string())
+                .unwrap()
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let march = format_month(2024, 3, true, today, &opts);
+        assert_eq!(march[6], "24 25 26 27 28 29 \u{1b}[7m30\u{1b}[0m");
+        assert_eq!(march[7], "\u{1b}[7m31\u{1b}[0m                  ");
+        let april = format_month(2024, 4, true, today, &opts);
+        assert_eq!(
+            april[2],
+            "   \u{1b}[7m 1\u{1b}[0m \u{1b}[7m 2\u{1b}[0m  3  4  5  6"
+        );
+    }
 }
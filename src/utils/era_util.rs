@@ -0,0 +1,89 @@
+use chrono::{Datelike, NaiveDate};
+
+/// 元号の一覧(元号名, 開始日)
+/// 明治より前の日付は元号で表示しない。
+pub const JAPANESE_ERAS: [(&str, (i32, u32, u32)); 5] = [
+    ("明治", (1868, 10, 23)),
+    ("大正", (1912, 7, 30)),
+    ("昭和", (1926, 12, 25)),
+    ("平成", (1989, 1, 8)),
+    ("令和", (2019, 5, 1)),
+];
+
+/// 対象日の元号と和暦の年を取得
+///
+/// * `date` - 対象日
+pub fn japanese_era(date: NaiveDate) -> Option<(&'static str, i32)> {
+    JAPANESE_ERAS
+        .iter()
+        .rev()
+        .find(|(_, (y, m, d))| NaiveDate::from_ymd_opt(*y, *m, *d).unwrap() <= date)
+        .map(|&(name, (start_year, _, _))| (name, date.year() - start_year + 1))
+}
+
+/// 対象日の和暦の年を`令和6年`の形式でフォーマットする(1年目は`元年`)
+///
+/// * `date` - 対象日
+pub fn format_japanese_era(date: NaiveDate) -> Option<String> {
+    japanese_era(date).map(|(name, year)| match year {
+        1 => format!("{name}元年"),
+        _ => format!("{name}{year}年"),
+    })
+}
+
+/// 対象年の和暦の年をフォーマットする
+/// 年の途中で改元された場合は`平成31年/令和元年`のように併記する。
+///
+/// * `year` - 対象年(西暦)
+pub fn format_japanese_era_year(year: i32) -> Option<String> {
+    let first = format_japanese_era(NaiveDate::from_ymd_opt(year, 1, 1).unwrap());
+    let last = format_japanese_era(NaiveDate::from_ymd_opt(year, 12, 31).unwrap())?;
+    match first {
+        Some(first) if first != last => Some(format!("{first}/{last}")),
+        _ => Some(last),
+    }
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{format_japanese_era, format_japanese_era_year, japanese_era};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_japanese_era() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        assert_eq!(japanese_era(date), Some(("令和", 6)));
+        let date = NaiveDate::from_ymd_opt(2019, 4, 30).unwrap();
+        assert_eq!(japanese_era(date), Some(("平成", 31)));
+        let date = NaiveDate::from_ymd_opt(2019, 5, 1).unwrap();
+        assert_eq!(japanese_era(date), Some(("令和", 1)));
+        let date = NaiveDate::from_ymd_opt(1989, 1, 7).unwrap();
+        assert_eq!(japanese_era(date), Some(("昭和", 64)));
+        let date = NaiveDate::from_ymd_opt(1868, 1, 1).unwrap();
+        assert_eq!(japanese_era(date), None);
+    }
+
+    #[test]
+    fn test_format_japanese_era() {
+        let date = NaiveDate::from_ymd_opt(2019, 5, 1).unwrap();
+        assert_eq!(format_japanese_era(date), Some("令和元年".to_string()));
+        let date = NaiveDate::from_ymd_opt(1926, 12, 1).unwrap();
+        assert_eq!(format_japanese_era(date), Some("大正15年".to_string()));
+    }
+
+    #[test]
+    fn test_format_japanese_era_year() {
+        assert_eq!(format_japanese_era_year(2024), Some("令和6年".to_string()));
+        assert_eq!(
+            format_japanese_era_year(2019),
+            Some("平成31年/令和元年".to_string())
+        );
+        assert_eq!(
+            format_japanese_era_year(1989),
+            Some("昭和64年/平成元年".to_string())
+        );
+        assert_eq!(format_japanese_era_year(1868), Some("明治元年".to_string()));
+        assert_eq!(format_japanese_era_year(1867), None);
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+/// イベントファイルの解析結果
+#[derive(Debug, Default)]
+pub struct Events {
+    /// イベントのある日付
+    pub dates: HashSet<NaiveDate>,
+    /// 解析できなかった行(行番号, 行の内容)
+    pub malformed: Vec<(usize, String)>,
+}
+
+/// イベントファイルの内容を解析
+/// 各行は`YYYY-MM-DD 説明`の形式(説明は省略可)。空行と`#`で始まる行は無視する。
+/// 解析できない行は処理を中断せず、行番号とともに`malformed`に記録する。
+///
+/// * `content` - イベントファイルの内容
+pub fn parse_events(content: &str) -> Events {
+    let mut events = Events::default();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let date = line.split_whitespace().next().unwrap_or_default();
+        match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => {
+                events.dates.insert(date);
+            }
+            _ => events.malformed.push((i + 1, line.to_string())),
+        }
+    }
+    events
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::parse_events;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_parse_events() {
+        let content = "\
+# holidays
+2024-03-20 Vernal Equinox Day
+
+2024-04-29 Showa Day
+2024-02-30 no such day
+tomorrow lunch
+2024-05-03
+";
+        let events = parse_events(content);
+        assert_eq!(events.dates.len(), 3);
+        assert!(events
+            .dates
+            .contains(&NaiveDate::from_ymd_opt(2024, 3, 20).unwrap()));
+        assert!(events
+            .dates
+            .contains(&NaiveDate::from_ymd_opt(2024, 5, 3).unwrap()));
+        assert_eq!(
+            events.malformed,
+            vec![
+                (5, "2024-02-30 no such day".to_string()),
+                (6, "tomorrow lunch".to_string()),
+            ]
+        );
+    }
+}
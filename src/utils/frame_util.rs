@@ -0,0 +1,197 @@
+use crate::utils::date_util::{visible_width, HeaderAlign};
+
+/// 月カレンダーを囲む罫線の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameStyle {
+    /// 罫線素片(┌─┐ │ └─┘)
+    Unicode,
+    /// ASCII文字(+-+ | +-+)
+    Ascii,
+}
+
+impl FrameStyle {
+    /// 罫線の構成文字(左上, 右上, 左下, 右下, 横線, 縦線)を返す
+    fn chars(&self) -> (char, char, char, char, char, char) {
+        match self {
+            FrameStyle::Unicode => ('┌', '┐', '└', '┘', '─', '│'),
+            FrameStyle::Ascii => ('+', '+', '+', '+', '-', '|'),
+        }
+    }
+}
+
+/// 枠線を付与した際に増える幅
+pub const FRAME_EXTRA_WIDTH: usize = 2;
+
+/// 月カレンダーを枠線で囲む
+/// `format_month`が生成した行の先頭(月ヘッダ)を上枠のタイトルに置き換え、
+/// 残りの行を左右の縦線で囲み、最後に下枠を追加する。
+/// タイトルは月ヘッダの寄せ方に従い、左寄せ・右寄せの場合は角との間に横線を1文字残す。
+///
+/// * `lines` - `format_month`が生成した行
+/// * `style` - 罫線の種類
+/// * `align` - 月ヘッダの寄せ方
+pub fn frame_month(lines: Vec<String>, style: FrameStyle, align: HeaderAlign) -> Vec<String> {
+    let (top_left, top_right, bottom_left, bottom_right, horizontal, vertical) = style.chars();
+    let mut lines = lines.into_iter();
+
+    // 月ヘッダを上枠のタイトルに変換(枠内の幅は月ヘッダの幅)
+    let title = lines.next().unwrap_or_default();
+    let inner_width = visible_width(&title);
+    let title = format!(" {} ", title.trim());
+    let rest = inner_width - visible_width(&title).min(inner_width);
+    let left = match align {
+        HeaderAlign::Left => rest.min(1),
+        HeaderAlign::Center => rest / 2,
+        HeaderAlign::Right => rest - rest.min(1),
+    };
+    let mut framed = vec![format!(
+        "{top_left}{}{title}{}{top_right}",
+        horizontal.to_string().repeat(left),
+        horizontal.to_string().repeat(rest - left),
+    )];
+
+    // 曜日ヘッダと週の行を縦線で囲む(枠内に収まらない当日の閉じ括弧は省く)
+    framed.extend(lines.map(|mut line| {
+        if visible_width(&line) > inner_width {
+            if let Some(i) = line.rfind(']') {
+                line.remove(i);
+            }
+        }
+        format!("{vertical}{line}{vertical}")
+    }));
+
+    // 下枠を追加
+    framed.push(format!(
+        "{bottom_left}{}{bottom_right}",
+        horizontal.to_string().repeat(inner_width)
+    ));
+
+    framed
+}
+
+/// 月カレンダーを空白で囲む
+/// 枠線を付与した月と並べた際に幅と行数が揃うよう、左右に1桁ずつ空白を付与し、末尾に空行を追加する。
+///
+/// * `lines` - `format_month`が生成した行
+pub fn pad_month(lines: Vec<String>) -> Vec<String> {
+    let inner_width = lines.first().map_or(0, |line| visible_width(line));
+    // 当日の閉じ括弧がはみ出す行は右の空白を括弧に使う
+    let mut padded: Vec<String> = lines
+        .into_iter()
+        .map(|line| match visible_width(&line) > inner_width {
+            true => format!(" {line}"),
+            false => format!(" {line} "),
+        })
+        .collect();
+    padded.push(" ".repeat(inner_width + FRAME_EXTRA_WIDTH));
+    padded
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{frame_month, pad_month, FrameStyle};
+    use crate::utils::date_util::{format_month, FormatOptions, HeaderAlign};
+    use crate::utils::theme_util::Theme;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_frame_month() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions::default();
+        let may = vec![
+            "┌─────── May ────────┐",
+            "│Su Mo Tu We Th Fr Sa│",
+            "│                1  2│",
+            "│ 3  4  5  6  7  8  9│",
+            "│10 11 12 13 14 15 16│",
+            "│17 18 19 20 21 22 23│",
+            "│24 25 26 27 28 29 30│",
+            "│31                  │",
+            "└────────────────────┘",
+        ];
+        assert_eq!(
+            frame_month(
+                format_month(2020, 5, false, today, &opts),
+                FrameStyle::Unicode,
+                HeaderAlign::Center
+            ),
+            may
+        );
+
+        let leap_february = vec![
+            "+-- February 2020 ---+",
+            "|Su Mo Tu We Th Fr Sa|",
+            "|                   1|",
+            "| 2  3  4  5  6  7  8|",
+            "| 9 10 11 12 13 14 15|",
+            "|16 17 18 19 20 21 22|",
+            "|23 24 25 26 27 28 29|",
+            "|                    |",
+            "+--------------------+",
+        ];
+        assert_eq!(
+            frame_month(
+                format_month(2020, 2, true, today, &opts),
+                FrameStyle::Ascii,
+                HeaderAlign::Center
+            ),
+            leap_february
+        );
+    }
+
+    #[test]
+    fn test_frame_month_header_align() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions::default();
+        for (align, expected) in [
+            (HeaderAlign::Left, "+- March 2024 -------+"),
+            (HeaderAlign::Center, "+---- March 2024 ----+"),
+            (HeaderAlign::Right, "+------- March 2024 -+"),
+        ] {
+            let lines = format_month(2024, 3, true, today, &opts);
+            assert_eq!(frame_month(lines, FrameStyle::Ascii, align)[0], expected);
+        }
+    }
+
+    #[test]
+    fn test_frame_month_today_brackets() {
+        // 枠内に収まらない行末の閉じ括弧は省き、枠なしの月と並べる場合は右の空白を使う
+        let today = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+        let opts = FormatOptions {
+            today_brackets: true,
+            theme: Theme::plain(),
+            ..Default::default()
+        };
+        let lines = format_month(2024, 3, true, today, &opts);
+        assert_eq!(
+            frame_month(lines.clone(), FrameStyle::Ascii, HeaderAlign::Center)[4],
+            "|10 11 12 13 14 15[16|"
+        );
+        assert_eq!(pad_month(lines)[4], " 10 11 12 13 14 15[16]");
+    }
+
+    #[test]
+    fn test_frame_month_keeps_highlight() {
+        let today = NaiveDate::from_ymd_opt(2021, 4, 7).unwrap();
+        let opts = FormatOptions::default();
+        let framed = frame_month(
+            format_month(2021, 4, true, today, &opts),
+            FrameStyle::Unicode,
+            HeaderAlign::Center,
+        );
+        assert_eq!(framed[3], "│ 4  5  6 \u{1b}[7m 7\u{1b}[0m  8  9 10│");
+    }
+
+    #[test]
+    fn test_pad_month() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions::default();
+        let lines = format_month(2020, 5, false, today, &opts);
+        let framed = frame_month(lines.clone(), FrameStyle::Unicode, HeaderAlign::Center);
+        let padded = pad_month(lines);
+        assert_eq!(padded.len(), framed.len());
+        assert_eq!(padded[2], "                 1  2 ");
+        assert_eq!(padded[8], " ".repeat(22));
+    }
+}
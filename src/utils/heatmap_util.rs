@@ -0,0 +1,139 @@
+use crate::utils::date_util::{is_highlighted, is_today, leading_blanks, FormatOptions};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// 平日の記号
+const DAY_MARK: char = '.';
+/// 週末の記号
+const WEEKEND_MARK: char = '+';
+/// 強調表示する日付・イベントのある日付の記号
+const EVENT_MARK: char = '#';
+/// 当日の記号
+const TODAY_MARK: char = '@';
+/// 曜日ラベルの列の幅(曜日名2文字と区切りの空白)
+const LABEL_WIDTH: usize = 3;
+
+/// 対象年の全日付を1日1文字のヒートマップとして生成
+/// 行を曜日(週の始まりの曜日から7行)、列を週として並べ、先頭行に各月の始まる列へ月名を置く。
+/// 当日・強調表示する日付・イベントのある日付・週末は記号で区別し、配色に従って装飾する。
+///
+/// * `year`  - 対象年
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+pub fn render_heatmap(year: i32, today: NaiveDate, opts: &FormatOptions) -> String {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let leading = leading_blanks(opts.first_day, first.weekday());
+    let days: Vec<NaiveDate> = first
+        .iter_days()
+        .take_while(|date| date.year() == year)
+        .collect();
+    // 1月1日の前の空白を含めて7日毎に1列(最大54列)
+    let columns = (leading + days.len()).div_ceil(7);
+
+    // 月名の行(前の月名と重なる場合は省略)
+    let mut header = " ".repeat(LABEL_WIDTH);
+    for (i, name) in opts.locale.month_names().iter().enumerate() {
+        let first = NaiveDate::from_ymd_opt(year, i as u32 + 1, 1).unwrap();
+        let column = LABEL_WIDTH + (leading + first.ordinal0() as usize) / 7;
+        let width = header.chars().count();
+        if i == 0 || column > width {
+            header.push_str(&" ".repeat(column - width));
+            header.extend(name.chars().take(3));
+        }
+    }
+    let mut lines = vec![header.trim_end().to_string()];
+
+    // 曜日毎の行
+    let mut weekday_names = opts.locale.weekday_names();
+    weekday_names.rotate_left(opts.first_day.num_days_from_sunday() as usize);
+    let mut rows: Vec<String> = weekday_names
+        .iter()
+        .map(|name| format!("{:<2} ", name.chars().take(2).collect::<String>()))
+        .collect();
+    for column in 0..columns {
+        for (row, line) in rows.iter_mut().enumerate() {
+            let cell = (column * 7 + row)
+                .checked_sub(leading)
+                .and_then(|index| days.get(index));
+            match cell {
+                Some(&date) => line.push_str(&format_mark(date, today, opts)),
+                None => line.push(' '),
+            }
+        }
+    }
+    lines.extend(rows.into_iter().map(|line| line.trim_end().to_string()));
+
+    lines.join("\n") + "\n"
+}
+
+/// 日付1日分の記号をフォーマットする
+///
+/// * `date`  - 対象日
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+fn format_mark(date: NaiveDate, today: NaiveDate, opts: &FormatOptions) -> String {
+    let theme = &opts.theme;
+    let (mark, style) = if is_today(date, today) {
+        (TODAY_MARK, theme.today_style())
+    } else if is_highlighted(date, &opts.highlights) {
+        (EVENT_MARK, theme.highlight_style())
+    } else if opts.events.contains(&date) {
+        (EVENT_MARK, theme.with_event_style(Default::default()))
+    } else if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        (WEEKEND_MARK, theme.weekend_style())
+    } else {
+        (DAY_MARK, Default::default())
+    };
+    style.paint(mark.to_string()).to_string()
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::render_heatmap;
+    use crate::utils::date_util::FormatOptions;
+    use crate::utils::theme_util::Theme;
+    use chrono::{NaiveDate, Weekday};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_render_heatmap() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let opts = FormatOptions {
+            events: HashSet::from([NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()]),
+            theme: Theme::plain(),
+            ..Default::default()
+        };
+        let heatmap = render_heatmap(2024, today, &opts);
+        let lines: Vec<&str> = heatmap.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with("   Jan Feb Mar  Apr"));
+        // 2024年1月1日は月曜日(日曜日始まりの2行目)
+        assert!(lines[1].starts_with("Su  +"));
+        assert!(lines[2].starts_with("Mo ...."));
+        assert!(lines[4].starts_with("We @..."));
+        assert!(lines[5].starts_with("Th #..."));
+        // 2024年は366日で、1月1日の前の空白1日を含めて53列
+        assert_eq!(lines[1].chars().count(), 3 + 53);
+        assert_eq!(lines[3].chars().count(), 3 + 53);
+        assert_eq!(lines[4].chars().count(), 3 + 52);
+    }
+
+    #[test]
+    fn test_render_heatmap_first_day() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions {
+            first_day: Weekday::Mon,
+            theme: Theme::plain(),
+            ..Default::default()
+        };
+        let heatmap = render_heatmap(2024, today, &opts);
+        let lines: Vec<&str> = heatmap.lines().collect();
+        assert!(lines[1].starts_with("Mo ...."));
+        assert!(lines[7].starts_with("Su ++++"));
+        let marks: usize = lines[1..]
+            .iter()
+            .map(|line| line[3..].chars().filter(|c| !c.is_whitespace()).count())
+            .sum();
+        assert_eq!(marks, 366);
+    }
+}
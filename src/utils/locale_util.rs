@@ -0,0 +1,292 @@
+use crate::utils::date_util::{MONTH_NAMES, WEEKDAY_NAMES};
+use anyhow::{bail, Result};
+use chrono::Weekday;
+
+/// 月名・曜日名を切り替える言語
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    /// 英語
+    #[default]
+    En,
+    /// ドイツ語
+    De,
+    /// スペイン語
+    Es,
+    /// フランス語
+    Fr,
+}
+
+/// ロケール(言語と任意の地域)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locale {
+    /// 言語
+    pub language: Language,
+    /// 地域(ISO 3166-1 alpha-2、大文字)
+    pub region: Option<String>,
+}
+
+/// 地域毎の週の始まりの曜日(`--first-day=auto`で使用)
+///
+/// 表にない地域は月曜日始まり、地域の指定がない場合は言語の既定
+/// (英語は日曜日、それ以外は月曜日)を用いる。
+pub const FIRST_DAY_BY_REGION: [(&str, Weekday); 24] = [
+    // 日曜日始まり
+    ("BR", Weekday::Sun),
+    ("CA", Weekday::Sun),
+    ("IL", Weekday::Sun),
+    ("IN", Weekday::Sun),
+    ("JP", Weekday::Sun),
+    ("KR", Weekday::Sun),
+    ("MX", Weekday::Sun),
+    ("PH", Weekday::Sun),
+    ("US", Weekday::Sun),
+    // 土曜日始まり
+    ("AE", Weekday::Sat),
+    ("AF", Weekday::Sat),
+    ("BH", Weekday::Sat),
+    ("DZ", Weekday::Sat),
+    ("EG", Weekday::Sat),
+    ("IQ", Weekday::Sat),
+    ("IR", Weekday::Sat),
+    ("JO", Weekday::Sat),
+    ("KW", Weekday::Sat),
+    ("LY", Weekday::Sat),
+    ("OM", Weekday::Sat),
+    ("QA", Weekday::Sat),
+    ("SA", Weekday::Sat),
+    ("SD", Weekday::Sat),
+    ("SY", Weekday::Sat),
+];
+
+impl Locale {
+    /// 数値を桁区切り・小数点の慣習に従ってフォーマットする
+    /// 英語は`1,234.5`、ドイツ語・スペイン語は`1.234,5`、フランス語は`1 234,5`(狭い空白で区切る)。
+    ///
+    /// * `value`    - 数値
+    /// * `decimals` - 小数点以下の桁数
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        let (grouping, decimal) = match self.language {
+            Language::En => (',', '.'),
+            Language::De | Language::Es => ('.', ','),
+            Language::Fr => ('\u{202f}', ','),
+        };
+        let formatted = format!("{:.decimals$}", value.abs());
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+        let mut number = String::new();
+        if value < 0.0 {
+            number.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                number.push(grouping);
+            }
+            number.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            number.push(decimal);
+            number.push_str(fraction);
+        }
+        number
+    }
+
+    /// 慣習上の週の始まりの曜日
+    pub fn first_day(&self) -> Weekday {
+        match self.region {
+            Some(ref region) => FIRST_DAY_BY_REGION
+                .iter()
+                .find(|(code, _)| code == region)
+                .map_or(Weekday::Mon, |&(_, weekday)| weekday),
+            None => match self.language {
+                Language::En => Weekday::Sun,
+                Language::De | Language::Es | Language::Fr => Weekday::Mon,
+            },
+        }
+    }
+
+    /// 月名(1月〜12月)
+    pub fn month_names(&self) -> [&'static str; 12] {
+        match self.language {
+            Language::En => MONTH_NAMES,
+            Language::De => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Language::Es => [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            Language::Fr => [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+        }
+    }
+
+    /// 曜日名(日曜日〜土曜日)
+    pub fn weekday_names(&self) -> [&'static str; 7] {
+        match self.language {
+            Language::En => WEEKDAY_NAMES,
+            Language::De => [
+                "Sonntag",
+                "Montag",
+                "Dienstag",
+                "Mittwoch",
+                "Donnerstag",
+                "Freitag",
+                "Samstag",
+            ],
+            Language::Es => [
+                "domingo",
+                "lunes",
+                "martes",
+                "miércoles",
+                "jueves",
+                "viernes",
+                "sábado",
+            ],
+            Language::Fr => [
+                "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+            ],
+        }
+    }
+}
+
+/// 引数・ロケールの解析
+/// 受け取ったロケール(`fr`、`fr-CA`、`fr_CA`など)を解析してLocale型に変換して返す。
+///
+/// * `locale` - ロケール
+pub fn parse_locale(locale: &str) -> Result<Locale> {
+    let (language, region) = match locale.split_once(['-', '_']) {
+        Some((language, region)) => (language, Some(region)),
+        None => (locale, None),
+    };
+
+    let language = match language.to_lowercase().as_str() {
+        "en" => Language::En,
+        "de" => Language::De,
+        "es" => Language::Es,
+        "fr" => Language::Fr,
+        _ => bail!(r#"Invalid locale "{locale}""#),
+    };
+
+    let region = match region {
+        Some(region) if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) => {
+            Some(region.to_uppercase())
+        }
+        Some(_) => bail!(r#"Invalid locale "{locale}""#),
+        None => None,
+    };
+
+    Ok(Locale { language, region })
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{parse_locale, Language, Locale};
+    use chrono::Weekday;
+
+    #[test]
+    fn test_parse_locale() {
+        let res = parse_locale("fr");
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Locale {
+                language: Language::Fr,
+                region: None
+            }
+        );
+
+        let res = parse_locale("de-AT");
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Locale {
+                language: Language::De,
+                region: Some("AT".to_string())
+            }
+        );
+
+        let res = parse_locale("en_us");
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().region, Some("US".to_string()));
+
+        let res = parse_locale("xx");
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid locale "xx""#);
+
+        let res = parse_locale("fr-123");
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid locale "fr-123""#);
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(Locale::default().format_number(1234.5, 1), "1,234.5");
+        assert_eq!(
+            parse_locale("de").unwrap().format_number(1234.5, 1),
+            "1.234,5"
+        );
+        assert_eq!(
+            parse_locale("fr").unwrap().format_number(1234567.0, 0),
+            "1\u{202f}234\u{202f}567"
+        );
+        assert_eq!(parse_locale("es").unwrap().format_number(26.78, 1), "26,8");
+        assert_eq!(Locale::default().format_number(-1000.0, 0), "-1,000");
+    }
+
+    #[test]
+    fn test_names() {
+        let locale = parse_locale("es").unwrap();
+        assert_eq!(locale.month_names()[0], "enero");
+        assert_eq!(locale.weekday_names()[6], "sábado");
+        assert_eq!(Locale::default().month_names()[11], "December");
+        assert_eq!(Locale::default().weekday_names()[0], "Sunday");
+    }
+
+    #[test]
+    fn test_first_day() {
+        assert_eq!(Locale::default().first_day(), Weekday::Sun);
+        assert_eq!(parse_locale("en").unwrap().first_day(), Weekday::Sun);
+        assert_eq!(parse_locale("en-GB").unwrap().first_day(), Weekday::Mon);
+        assert_eq!(parse_locale("fr").unwrap().first_day(), Weekday::Mon);
+        assert_eq!(parse_locale("fr-CA").unwrap().first_day(), Weekday::Sun);
+        assert_eq!(parse_locale("es-MX").unwrap().first_day(), Weekday::Sun);
+        assert_eq!(parse_locale("de-AT").unwrap().first_day(), Weekday::Mon);
+        assert_eq!(parse_locale("en-AE").unwrap().first_day(), Weekday::Sat);
+    }
+}
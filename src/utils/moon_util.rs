@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+
+/// 朔望月の平均の長さ(日)
+const SYNODIC_MONTH: f64 = 29.530588853;
+
+/// 月相の記号(新月から順に8相)
+pub const MOON_PHASES: [&str; 8] = ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"];
+
+/// 対象日の月齢を取得(平均朔望月による近似)
+/// 2000年1月6日18時14分(UT)の新月を基準に、対象日の正午までの経過日数から算出する。
+///
+/// * `date` - 対象日
+pub fn moon_age(date: NaiveDate) -> f64 {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 6).unwrap();
+    // 基準の新月は18時14分、対象日は正午とする
+    let days = date.signed_duration_since(epoch).num_days() as f64 + 0.5 - 0.76;
+    days.rem_euclid(SYNODIC_MONTH)
+}
+
+/// 対象日の月相の記号を取得
+/// 月齢を8相に丸めて、新月(🌑)〜有明月(🌘)の記号を返す。
+///
+/// * `date` - 対象日
+pub fn moon_phase(date: NaiveDate) -> &'static str {
+    let phase = (moon_age(date) / SYNODIC_MONTH * 8.0).round() as usize % 8;
+    MOON_PHASES[phase]
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{moon_age, moon_phase};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_moon_phase() {
+        // 2024年4月8日の皆既日食(新月)
+        let date = NaiveDate::from_ymd_opt(2024, 4, 8).unwrap();
+        assert_eq!(moon_phase(date), "🌑");
+        assert!(!(1.0..28.5).contains(&moon_age(date)));
+        // 2024年3月25日の満月
+        let date = NaiveDate::from_ymd_opt(2024, 3, 25).unwrap();
+        assert_eq!(moon_phase(date), "🌕");
+        // 2024年3月17日の上弦
+        let date = NaiveDate::from_ymd_opt(2024, 3, 17).unwrap();
+        assert_eq!(moon_phase(date), "🌓");
+    }
+}
@@ -0,0 +1,173 @@
+use crate::utils::date_util::{format_grid, FormatOptions};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// ペルシア暦(ジャラーリー暦)の月名
+pub const PERSIAN_MONTH_NAMES: [&str; 12] = [
+    "Farvardin",
+    "Ordibehesht",
+    "Khordad",
+    "Tir",
+    "Mordad",
+    "Shahrivar",
+    "Mehr",
+    "Aban",
+    "Azar",
+    "Dey",
+    "Bahman",
+    "Esfand",
+];
+
+/// グレゴリオ暦の日付をペルシア暦の(年, 月, 日)に変換
+/// 33年周期の算術規則による。
+///
+/// * `date` - グレゴリオ暦の日付
+pub fn to_jalali(date: NaiveDate) -> (i32, u32, u32) {
+    const DAYS_BEFORE_MONTH: [i64; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+    let (gy, gm, gd) = (
+        date.year() as i64,
+        date.month0() as usize,
+        date.day() as i64,
+    );
+    let gy2 = if gm > 1 { gy + 1 } else { gy };
+    let mut days = 355666 + 365 * gy + (gy2 + 3) / 4 - (gy2 + 99) / 100
+        + (gy2 + 399) / 400
+        + gd
+        + DAYS_BEFORE_MONTH[gm];
+    let mut jy = -1595 + 33 * (days / 12053);
+    days %= 12053;
+    jy += 4 * (days / 1461);
+    days %= 1461;
+    if days > 365 {
+        jy += (days - 1) / 365;
+        days = (days - 1) % 365;
+    }
+    let (jm, jd) = if days < 186 {
+        (1 + days / 31, 1 + days % 31)
+    } else {
+        (7 + (days - 186) / 30, 1 + (days - 186) % 30)
+    };
+    (jy as i32, jm as u32, jd as u32)
+}
+
+/// ペルシア暦の(年, 月, 日)をグレゴリオ暦の日付に変換
+///
+/// * `year`  - ペルシア暦の年
+/// * `month` - ペルシア暦の月
+/// * `day`   - ペルシア暦の日
+pub fn from_jalali(year: i32, month: u32, day: u32) -> NaiveDate {
+    let jy = year as i64 + 1595;
+    let (jm, jd) = (month as i64, day as i64);
+    // グレゴリオ暦0年1月1日からの経過日数
+    let days = -355668
+        + 365 * jy
+        + (jy / 33) * 8
+        + ((jy % 33) + 3) / 4
+        + jd
+        + if jm < 7 {
+            (jm - 1) * 31
+        } else {
+            (jm - 7) * 30 + 186
+        };
+    NaiveDate::from_ymd_opt(0, 1, 1).unwrap() + Days::new(days as u64)
+}
+
+/// ペルシア暦の対象月の各日を取得
+/// 各日のグレゴリオ暦の日付とペルシア暦の日の組を返す。
+///
+/// * `year`  - ペルシア暦の年
+/// * `month` - ペルシア暦の月
+pub fn jalali_month_days(year: i32, month: u32) -> Vec<(NaiveDate, u32)> {
+    let first = from_jalali(year, month, 1);
+    first
+        .iter_days()
+        .map(|date| (date, to_jalali(date)))
+        .take_while(|&(_, (y, m, _))| (y, m) == (year, month))
+        .map(|(date, (_, _, d))| (date, d))
+        .collect()
+}
+
+/// ペルシア暦の対象月をカレンダー形式フォーマットする
+///
+/// * `year`     - ペルシア暦の年
+/// * `month`    - ペルシア暦の月
+/// * `add_year` - 年ヘッダを追加するか否か
+/// * `today`    - 当日日付(グレゴリオ暦)
+/// * `opts`     - フォーマット設定
+pub fn format_persian_month(
+    year: i32,
+    month: u32,
+    add_year: bool,
+    today: NaiveDate,
+    opts: &FormatOptions,
+) -> Vec<String> {
+    let month_name = PERSIAN_MONTH_NAMES[month as usize - 1];
+    let title = if add_year {
+        format!("{month_name} {}", opts.format_year(year))
+    } else {
+        month_name.to_string()
+    };
+    format_grid(&title, &jalali_month_days(year, month), today, opts)
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{format_persian_month, from_jalali, jalali_month_days, to_jalali};
+    use crate::utils::date_util::FormatOptions;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_to_jalali() {
+        // ノウルーズ(新年)
+        let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        assert_eq!(to_jalali(date), (1403, 1, 1));
+        let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+        assert_eq!(to_jalali(date), (1404, 1, 1));
+        let date = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+        assert_eq!(to_jalali(date), (1403, 12, 30));
+        let date = NaiveDate::from_ymd_opt(2024, 10, 14).unwrap();
+        assert_eq!(to_jalali(date), (1403, 7, 23));
+    }
+
+    #[test]
+    fn test_from_jalali() {
+        assert_eq!(
+            from_jalali(1403, 1, 1),
+            NaiveDate::from_ymd_opt(2024, 3, 20).unwrap()
+        );
+        assert_eq!(
+            from_jalali(1403, 12, 30),
+            NaiveDate::from_ymd_opt(2025, 3, 20).unwrap()
+        );
+        assert_eq!(
+            from_jalali(1402, 10, 11),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_jalali_month_days() {
+        assert_eq!(jalali_month_days(1403, 1).len(), 31);
+        assert_eq!(jalali_month_days(1403, 7).len(), 30);
+        // 1403年は閏年、1404年は平年
+        assert_eq!(jalali_month_days(1403, 12).len(), 30);
+        assert_eq!(jalali_month_days(1404, 12).len(), 29);
+    }
+
+    #[test]
+    fn test_format_persian_month() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 21).unwrap();
+        let opts = FormatOptions::default();
+        let farvardin = vec![
+            "   Farvardin 1403   ",
+            "Su Mo Tu We Th Fr Sa",
+            "          1 \u{1b}[7m 2\u{1b}[0m  3  4",
+            " 5  6  7  8  9 10 11",
+            "12 13 14 15 16 17 18",
+            "19 20 21 22 23 24 25",
+            "26 27 28 29 30 31   ",
+            "                    ",
+        ];
+        assert_eq!(format_persian_month(1403, 1, true, today, &opts), farvardin);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::utils::date_util::{
+    is_highlighted, is_today, leading_blanks, month_days, FormatOptions,
+};
+use chrono::{Datelike, NaiveDate};
+
+/// 日付セル1つ分の幅(px)
+const CELL_WIDTH: usize = 24;
+/// 1行分の高さ(px)
+const CELL_HEIGHT: usize = 20;
+/// 月カレンダー同士の間隔(px)
+const MONTH_GAP: usize = 16;
+/// 月カレンダー1つ分の行数(月ヘッダ + 曜日ヘッダ + 最大6週)
+const MONTH_ROWS: usize = 8;
+/// 強調表示するセルの背景色
+const HIGHLIGHT_FILL: &str = "#cccccc";
+
+/// 対象年月のカレンダーをSVG画像として生成
+/// 各セルを等幅レイアウトの`<text>`要素として配置し、
+/// 当日・強調表示する日付のセルには背景の`<rect>`を描画する。
+///
+/// * `year_months`    - 対象年月タプルのVec
+/// * `today`          - 当日日付
+/// * `opts`           - フォーマット設定
+/// * `months_per_row` - 1行に並べる月数
+pub fn render_svg(
+    year_months: &[(i32, u32)],
+    today: NaiveDate,
+    opts: &FormatOptions,
+    months_per_row: usize,
+) -> String {
+    let month_width = CELL_WIDTH * 7;
+    let month_height = CELL_HEIGHT * MONTH_ROWS;
+    let columns = year_months.len().clamp(1, months_per_row);
+    let rows = year_months.len().div_ceil(months_per_row).max(1);
+    let width = columns * month_width + (columns - 1) * MONTH_GAP;
+    let height = rows * month_height + (rows - 1) * MONTH_GAP;
+
+    let mut elements = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace" font-size="14">"#
+    )];
+    for (i, &(year, month)) in year_months.iter().enumerate() {
+        let left = (i % months_per_row) * (month_width + MONTH_GAP);
+        let top = (i / months_per_row) * (month_height + MONTH_GAP);
+        elements.extend(render_month(year, month, today, opts, left, top));
+    }
+    elements.push(String::from("</svg>"));
+
+    elements.join("\n") + "\n"
+}
+
+/// 月カレンダー1つ分のSVG要素を生成
+///
+/// * `year`  - 年
+/// * `month` - 月
+/// * `today` - 当日日付
+/// * `opts`  - フォーマット設定
+/// * `left`  - 左端のx座標
+/// * `top`   - 上端のy座標
+fn render_month(
+    year: i32,
+    month: u32,
+    today: NaiveDate,
+    opts: &FormatOptions,
+    left: usize,
+    top: usize,
+) -> Vec<String> {
+    // 文字のベースラインはセル下端から少し上に置く
+    let baseline = |row: usize| top + (row + 1) * CELL_HEIGHT - 5;
+    // 数字は右寄せするため、x座標はセルの右端
+    let right = |column: usize| left + (column + 1) * CELL_WIDTH - 4;
+
+    // 月ヘッダ
+    let month_name = opts.locale.month_names()[month as usize - 1];
+    let mut elements = vec![format!(
+        r#"<text x="{}" y="{}" text-anchor="middle">{month_name} {}</text>"#,
+        left + CELL_WIDTH * 7 / 2,
+        baseline(0),
+        opts.format_header_year(year)
+    )];
+
+    // 曜日ヘッダ(週の始まりの曜日から並べる)
+    let mut weekday_names = opts.locale.weekday_names();
+    weekday_names.rotate_left(opts.first_day.num_days_from_sunday() as usize);
+    for (column, name) in weekday_names.iter().enumerate() {
+        let name: String = name.chars().take(opts.cell_width()).collect();
+        elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="end">{name}</text>"#,
+            right(column),
+            baseline(1)
+        ));
+    }
+
+    // 日付セル
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let leading = leading_blanks(opts.first_day, first.weekday());
+    for date in month_days(year, month) {
+        let index = leading + date.day0() as usize;
+        let (row, column) = (index / 7 + 2, index % 7);
+        if is_today(date, today) || is_highlighted(date, &opts.highlights) {
+            elements.push(format!(
+                r#"<rect x="{}" y="{}" width="{CELL_WIDTH}" height="{CELL_HEIGHT}" fill="{HIGHLIGHT_FILL}"/>"#,
+                left + column * CELL_WIDTH,
+                top + row * CELL_HEIGHT
+            ));
+        }
+        elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+            right(column),
+            baseline(row),
+            date.day()
+        ));
+    }
+
+    elements
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::render_svg;
+    use crate::utils::date_util::FormatOptions;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_render_svg() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let opts = FormatOptions::default();
+        let svg = render_svg(&[(2024, 3)], today, &opts, 3);
+        let lines: Vec<&str> = svg.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="168" height="160" font-family="monospace" font-size="14">"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"<text x="84" y="15" text-anchor="middle">March 2024</text>"#
+        );
+        assert_eq!(
+            lines[2],
+            r#"<text x="20" y="35" text-anchor="end">Su</text>"#
+        );
+        // 3月1日は金曜日
+        assert_eq!(
+            lines[9],
+            r#"<text x="140" y="55" text-anchor="end">1</text>"#
+        );
+        // 当日には背景の矩形を描画
+        assert_eq!(
+            lines[12],
+            r##"<rect x="24" y="60" width="24" height="20" fill="#cccccc"/>"##
+        );
+        assert_eq!(
+            lines[13],
+            r#"<text x="44" y="75" text-anchor="end">4</text>"#
+        );
+        assert_eq!(lines.last(), Some(&"</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 1);
+    }
+
+    #[test]
+    fn test_render_svg_rows() {
+        let today = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+        let opts = FormatOptions::default();
+        let year_months: Vec<_> = (1..=4).map(|month| (2024, month)).collect();
+        let svg = render_svg(&year_months, today, &opts, 3);
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="536" height="336""#)
+        );
+        assert!(svg.contains(r#"<text x="84" y="191" text-anchor="middle">April 2024</text>"#));
+        assert_eq!(svg.matches("<rect").count(), 0);
+    }
+}
@@ -0,0 +1,328 @@
+use ansi_term::{Colour, Style};
+use anyhow::{bail, Result};
+
+/// カレンダーの配色
+/// 当日・強調表示・繰り返しの曜日・イベント・週末・ヘッダ・週番号・月の初日と最終日の役割毎に装飾を持つ。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    today: Style,
+    highlight: Style,
+    recur: Style,
+    event: Style,
+    weekend: Style,
+    header: Style,
+    week_number: Style,
+    boundary: Style,
+}
+
+impl Default for Theme {
+    /// 既定の配色(当日・強調表示は反転、繰り返しの曜日は太字、イベントは下線)
+    fn default() -> Self {
+        Theme {
+            today: Style::new().reverse(),
+            highlight: Style::new().reverse(),
+            recur: Style::new().bold(),
+            event: Style::new().underline(),
+            weekend: Style::new(),
+            header: Style::new(),
+            week_number: Style::new(),
+            boundary: Style::new().underline(),
+        }
+    }
+}
+
+impl Theme {
+    /// Solarizedの配色(256色)
+    pub fn solarized() -> Self {
+        Theme {
+            today: Style::new().fg(Colour::Fixed(230)).on(Colour::Fixed(33)),
+            highlight: Style::new().fg(Colour::Fixed(230)).on(Colour::Fixed(37)),
+            recur: Style::new().fg(Colour::Fixed(37)).bold(),
+            event: Style::new().fg(Colour::Fixed(136)).underline(),
+            weekend: Style::new().fg(Colour::Fixed(166)),
+            header: Style::new().fg(Colour::Fixed(61)).bold(),
+            week_number: Style::new().fg(Colour::Fixed(64)),
+            boundary: Style::new().fg(Colour::Fixed(125)).underline(),
+        }
+    }
+
+    /// 色を使わない配色(反転・下線・太字のみ)
+    pub fn mono() -> Self {
+        Theme {
+            header: Style::new().bold(),
+            ..Theme::default()
+        }
+    }
+
+    /// 装飾を一切行わない配色(`--color=never`)
+    pub fn plain() -> Self {
+        Theme {
+            today: Style::new(),
+            highlight: Style::new(),
+            recur: Style::new(),
+            event: Style::new(),
+            weekend: Style::new(),
+            header: Style::new(),
+            week_number: Style::new(),
+            boundary: Style::new(),
+        }
+    }
+
+    /// 当日の装飾を差し替えた配色
+    ///
+    /// * `today` - 当日の装飾
+    pub fn with_today_style(self, today: Style) -> Self {
+        Theme { today, ..self }
+    }
+
+    /// 役割毎の文字色の指定を重ねた配色
+    /// `today=red,events=green`の形式で、役割毎に文字色を上書きする(反転・下線などの属性は残す)。
+    /// 役割は`today`・`highlights`・`recur`・`events`・`weekends`・`header`・`week-numbers`・`boundaries`。
+    ///
+    /// * `spec`      - 役割毎の文字色の指定
+    /// * `truecolor` - `#rrggbb`の色を24ビットカラーで出力するか否か(しない場合は基本の8色に丸める)
+    pub fn with_style_spec(self, spec: &str, truecolor: bool) -> Result<Self> {
+        let mut theme = self;
+        for item in spec.split(',') {
+            let Some((category, colour)) = item.split_once('=') else {
+                bail!(r#"Invalid style "{item}""#);
+            };
+            let colour = parse_colour(colour.trim(), truecolor)?;
+            let style = match category.trim().to_lowercase().as_str() {
+                "today" => &mut theme.today,
+                "highlight" | "highlights" => &mut theme.highlight,
+                "recur" | "recurrences" => &mut theme.recur,
+                "event" | "events" => &mut theme.event,
+                "weekend" | "weekends" => &mut theme.weekend,
+                "header" | "headers" => &mut theme.header,
+                "week-number" | "week-numbers" => &mut theme.week_number,
+                "boundary" | "boundaries" => &mut theme.boundary,
+                _ => bail!(r#"Invalid style category "{}""#, category.trim()),
+            };
+            style.foreground = Some(colour);
+        }
+        Ok(theme)
+    }
+
+    /// 当日の装飾
+    pub fn today_style(&self) -> Style {
+        self.today
+    }
+
+    /// 強調表示する日付の装飾
+    pub fn highlight_style(&self) -> Style {
+        self.highlight
+    }
+
+    /// 繰り返し強調表示する曜日の装飾(`--recur`)
+    pub fn recur_style(&self) -> Style {
+        self.recur
+    }
+
+    /// 週末の装飾
+    pub fn weekend_style(&self) -> Style {
+        self.weekend
+    }
+
+    /// 月ヘッダ・曜日ヘッダの装飾
+    pub fn header_style(&self) -> Style {
+        self.header
+    }
+
+    /// 週番号の装飾
+    pub fn week_number_style(&self) -> Style {
+        self.week_number
+    }
+
+    /// 月の初日・最終日の装飾(`--mark-boundaries`)
+    pub fn boundary_style(&self) -> Style {
+        self.boundary
+    }
+
+    /// イベントのある日付の装飾を重ねる
+    /// 元の装飾に、イベントの装飾で指定された色・属性を上書きする。
+    ///
+    /// * `base` - 元の装飾
+    pub fn with_event_style(&self, base: Style) -> Style {
+        let event = self.event;
+        Style {
+            foreground: event.foreground.or(base.foreground),
+            background: event.background.or(base.background),
+            is_bold: base.is_bold || event.is_bold,
+            is_dimmed: base.is_dimmed || event.is_dimmed,
+            is_italic: base.is_italic || event.is_italic,
+            is_underline: base.is_underline || event.is_underline,
+            is_blink: base.is_blink || event.is_blink,
+            is_reverse: base.is_reverse || event.is_reverse,
+            is_hidden: base.is_hidden || event.is_hidden,
+            is_strikethrough: base.is_strikethrough || event.is_strikethrough,
+        }
+    }
+}
+
+/// 色名(または256色の番号、`#rrggbb`)を解析
+///
+/// * `name`      - 色名(`black`・`red`・`green`・`yellow`・`blue`・`purple`・`cyan`・`white`)、0〜255、`#rrggbb`
+/// * `truecolor` - `#rrggbb`を24ビットカラーのまま使うか否か
+fn parse_colour(name: &str, truecolor: bool) -> Result<Colour> {
+    if name.starts_with('#') {
+        return match parse_hex_colour(name) {
+            Some(colour) if truecolor => Ok(colour),
+            Some(colour) => Ok(to_basic_colour(colour)),
+            None => bail!(r#"Invalid color "{name}""#),
+        };
+    }
+    let colour = match name.to_lowercase().as_str() {
+        "black" => Colour::Black,
+        "red" => Colour::Red,
+        "green" => Colour::Green,
+        "yellow" => Colour::Yellow,
+        "blue" => Colour::Blue,
+        "purple" | "magenta" => Colour::Purple,
+        "cyan" => Colour::Cyan,
+        "white" => Colour::White,
+        _ => match name.parse() {
+            Ok(number) => Colour::Fixed(number),
+            _ => bail!(r#"Invalid color "{name}""#),
+        },
+    };
+    Ok(colour)
+}
+
+/// `#rrggbb`形式の色を解析
+///
+/// * `hex` - `#`に続く6桁の16進数
+pub fn parse_hex_colour(hex: &str) -> Option<Colour> {
+    let digits = hex.strip_prefix('#').filter(|digits| digits.len() == 6)?;
+    let channel = |i: usize| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok();
+    Some(Colour::RGB(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// 24ビットカラーを基本の8色に丸める(各成分の明暗で判定する)
+///
+/// * `colour` - 対象の色
+fn to_basic_colour(colour: Colour) -> Colour {
+    let Colour::RGB(r, g, b) = colour else {
+        return colour;
+    };
+    match (r >= 128, g >= 128, b >= 128) {
+        (false, false, false) => Colour::Black,
+        (true, false, false) => Colour::Red,
+        (false, true, false) => Colour::Green,
+        (true, true, false) => Colour::Yellow,
+        (false, false, true) => Colour::Blue,
+        (true, false, true) => Colour::Purple,
+        (false, true, true) => Colour::Cyan,
+        (true, true, true) => Colour::White,
+    }
+}
+
+// ---------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::{parse_hex_colour, Theme};
+    use ansi_term::{Colour, Style};
+
+    #[test]
+    fn test_with_event_style() {
+        let theme = Theme::default();
+        assert_eq!(
+            theme.with_event_style(theme.today_style()),
+            Style::new().reverse().underline()
+        );
+        assert_eq!(
+            theme.with_event_style(Style::new()),
+            Style::new().underline()
+        );
+        assert_eq!(Theme::plain().with_event_style(Style::new()), Style::new());
+    }
+
+    #[test]
+    fn test_with_today_style() {
+        let theme = Theme::solarized().with_today_style(Style::new().underline());
+        assert_eq!(theme.today_style(), Style::new().underline());
+        assert_eq!(theme.header_style(), Theme::solarized().header_style());
+    }
+
+    #[test]
+    fn test_with_style_spec() {
+        let theme = Theme::default()
+            .with_style_spec("today=red,events=green,weekends=blue,boundaries=208", false)
+            .unwrap();
+        assert_eq!(theme.today_style(), Style::new().reverse().fg(Colour::Red));
+        assert_eq!(
+            theme.with_event_style(Style::new()),
+            Style::new().underline().fg(Colour::Green)
+        );
+        assert_eq!(theme.weekend_style(), Style::new().fg(Colour::Blue));
+        assert_eq!(
+            theme.boundary_style(),
+            Style::new().underline().fg(Colour::Fixed(208))
+        );
+        assert_eq!(theme.header_style(), Style::new());
+
+        let res = Theme::default().with_style_spec("moon=red", false);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            r#"Invalid style category "moon""#
+        );
+        let res = Theme::default().with_style_spec("today=pink", false);
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid color "pink""#);
+        let res = Theme::default().with_style_spec("today", false);
+        assert_eq!(res.unwrap_err().to_string(), r#"Invalid style "today""#);
+    }
+
+    #[test]
+    fn test_parse_hex_colour() {
+        assert_eq!(
+            parse_hex_colour("#268bd2"),
+            Some(Colour::RGB(0x26, 0x8b, 0xd2))
+        );
+        assert_eq!(
+            parse_hex_colour("#FFFFFF"),
+            Some(Colour::RGB(255, 255, 255))
+        );
+        assert_eq!(parse_hex_colour("#fff"), None);
+        assert_eq!(parse_hex_colour("#gg0000"), None);
+        assert_eq!(parse_hex_colour("268bd2"), None);
+    }
+
+    #[test]
+    fn test_with_style_spec_truecolor() {
+        let theme = Theme::default()
+            .with_style_spec("weekends=#268bd2", true)
+            .unwrap();
+        assert_eq!(
+            theme.weekend_style(),
+            Style::new().fg(Colour::RGB(0x26, 0x8b, 0xd2))
+        );
+        // 24ビットカラーを使わない場合は基本の8色に丸める
+        let theme = Theme::default()
+            .with_style_spec("weekends=#268bd2,today=#d33682", false)
+            .unwrap();
+        assert_eq!(theme.weekend_style(), Style::new().fg(Colour::Cyan));
+        assert_eq!(
+            theme.today_style(),
+            Style::new().reverse().fg(Colour::Purple)
+        );
+        let res = Theme::default().with_style_spec("today=#12345", true);
+        assert_eq!(res.unwrap_err().to_string(), r##"Invalid color "#12345""##);
+    }
+
+    #[test]
+    fn test_mono() {
+        let theme = Theme::mono();
+        for style in [
+            theme.today_style(),
+            theme.highlight_style(),
+            theme.weekend_style(),
+            theme.header_style(),
+            theme.week_number_style(),
+            theme.boundary_style(),
+            theme.with_event_style(Style::new()),
+        ] {
+            assert_eq!(style.foreground, None);
+            assert_eq!(style.background, None);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::utils::browse_util::{parse_key, MonthBrowser};
+use crate::utils::date_util::{format_month, FormatOptions};
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use std::io::{self, Read, Write};
+
+/// 画面下部の操作の説明
+const HELP: &str = "←/→ month  t today  q quit";
+
+/// 端末の入力を1文字ずつ受け取る設定(破棄時に元の設定へ戻す)
+struct RawMode {
+    /// 元の端末の設定
+    original: libc::termios,
+}
+
+impl RawMode {
+    /// 標準入力の行バッファ・エコー・シグナルを無効にする
+    fn enable() -> io::Result<Self> {
+        // SAFETY: termiosは全て0で初期化してからtcgetattrで埋める
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = termios;
+        termios.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        termios.c_cc[libc::VMIN] = 1;
+        termios.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(RawMode { original })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// 1ヶ月ずつ切り替えて表示する全画面の画面を開く
+/// 代替画面に`format_month`の出力を描画し、キー入力毎に描き直す。`q`で終了して端末を元に戻す。
+///
+/// * `browser` - 最初に表示する年月の状態
+/// * `today`   - 当日日付
+/// * `opts`    - フォーマット設定
+pub fn browse(mut browser: MonthBrowser, today: NaiveDate, opts: &FormatOptions) -> Result<()> {
+    let raw_mode = match RawMode::enable() {
+        Ok(raw_mode) => raw_mode,
+        Err(e) => bail!("--interactive requires a terminal: {e}"),
+    };
+    let mut out = io::stdout().lock();
+    // 代替画面に切り替えてカーソルを隠す
+    write!(out, "\u{1b}[?1049h\u{1b}[?25l")?;
+    let result = browse_loop(&mut out, &mut browser, today, opts);
+    write!(out, "\u{1b}[?25h\u{1b}[?1049l")?;
+    out.flush()?;
+    drop(raw_mode);
+    result
+}
+
+/// 描画とキー入力の処理を終了のキーまで繰り返す
+///
+/// * `out`     - 出力先
+/// * `browser` - 表示中の年月の状態
+/// * `today`   - 当日日付
+/// * `opts`    - フォーマット設定
+fn browse_loop(
+    out: &mut dyn Write,
+    browser: &mut MonthBrowser,
+    today: NaiveDate,
+    opts: &FormatOptions,
+) -> Result<()> {
+    let mut input = io::stdin().lock();
+    let mut buffer = [0u8; 8];
+    loop {
+        write!(out, "\u{1b}[H\u{1b}[2J")?;
+        for line in format_month(browser.year, browser.month, true, today, opts) {
+            writeln!(out, "{line}")?;
+        }
+        writeln!(out, "{HELP}")?;
+        out.flush()?;
+
+        let read = input.read(&mut buffer)?;
+        if read == 0 || !browser.handle(parse_key(&buffer[..read])) {
+            return Ok(());
+        }
+    }
+}
@@ -6,21 +6,31 @@ use std::fs;
 
 const PRG: &str = "calr";
 
+/// 実行環境に左右されないよう、環境変数「COLUMNS」を除き、空の設定ファイルを使うコマンドを生成
+fn calr() -> Result<Command> {
+    let mut cmd = Command::cargo_bin(PRG)?;
+    cmd.env_remove("COLUMNS")
+        .env("CALR_CONFIG", "tests/inputs/empty.toml");
+    Ok(cmd)
+}
+
 // --------------------------------------------------
 #[test]
 fn dies_year_0() -> Result<()> {
-    Command::cargo_bin(PRG)?.arg("0").assert().failure().stderr(
-        predicate::str::contains(
+    calr()?
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
             "error: invalid value '0' for '[YEAR]': 0 is not in 1..=9999",
-        ),
-    );
+        ));
     Ok(())
 }
 
 // --------------------------------------------------
 #[test]
 fn dies_year_10000() -> Result<()> {
-    Command::cargo_bin(PRG)?
+    calr()?
         .arg("10000")
         .assert()
         .failure()
@@ -34,7 +44,7 @@ fn dies_year_10000() -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn dies_invalid_year() -> Result<()> {
-    Command::cargo_bin(PRG)?
+    calr()?
         .arg("foo")
         .assert()
         .failure()
@@ -48,10 +58,7 @@ fn dies_invalid_year() -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn dies_month_0() -> Result<()> {
-    let output = Command::cargo_bin(PRG)?
-        .args(["-m", "0"])
-        .output()
-        .expect("fail");
+    let output = calr()?.args(["-m", "0"]).output().expect("fail");
     assert!(!output.status.success());
 
     let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
@@ -66,10 +73,7 @@ fn dies_month_0() -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn dies_month_13() -> Result<()> {
-    let output = Command::cargo_bin(PRG)?
-        .args(["-m", "13"])
-        .output()
-        .expect("fail");
+    let output = calr()?.args(["-m", "13"]).output().expect("fail");
     assert!(!output.status.success());
 
     let stderr = String::from_utf8(output.stderr).expect("invalid UTF-8");
@@ -80,10 +84,7 @@ fn dies_month_13() -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn dies_invalid_month() -> Result<()> {
-    let output = Command::cargo_bin(PRG)?
-        .args(["-m", "foo"])
-        .output()
-        .expect("fail");
+    let output = calr()?.args(["-m", "foo"]).output().expect("fail");
     assert!(!output.status.success());
 
     let stderr = String::from_utf8(output.stderr).expect("invalid UTF-8");
@@ -95,7 +96,7 @@ fn dies_invalid_month() -> Result<()> {
 #[test]
 fn dies_y_and_month() -> Result<()> {
     let expected = "the argument '-m <MONTH>' cannot be used with '--year'";
-    Command::cargo_bin(PRG)?
+    calr()?
         .args(["-m", "1", "-y"])
         .assert()
         .failure()
@@ -107,7 +108,7 @@ fn dies_y_and_month() -> Result<()> {
 #[test]
 fn dies_y_and_year() -> Result<()> {
     let expected = "the argument '--year' cannot be used with '[YEAR]'";
-    Command::cargo_bin(PRG)?
+    calr()?
         .args(["-y", "2000"])
         .assert()
         .failure()
@@ -134,7 +135,7 @@ fn month_num() -> Result<()> {
     ];
 
     for (num, month) in expected {
-        Command::cargo_bin(PRG)?
+        calr()?
             .args(["-m", num])
             .assert()
             .success()
@@ -161,7 +162,7 @@ fn partial_month() -> Result<()> {
     ];
 
     for (arg, month) in expected {
-        Command::cargo_bin(PRG)?
+        calr()?
             .args(["-m", arg])
             .assert()
             .success()
@@ -173,7 +174,7 @@ fn partial_month() -> Result<()> {
 // --------------------------------------------------
 fn run(args: &[&str], expected_file: &str) -> Result<()> {
     let expected = fs::read_to_string(expected_file)?;
-    let output = Command::cargo_bin(PRG)?.args(args).output().expect("fail");
+    let output = calr()?.args(args).output().expect("fail");
     assert!(output.status.success());
 
     let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
@@ -184,7 +185,7 @@ fn run(args: &[&str], expected_file: &str) -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn default_one_month() -> Result<()> {
-    let cmd = Command::cargo_bin(PRG)?.assert().success();
+    let cmd = calr()?.assert().success();
     let out = cmd.get_output();
     let stdout = String::from_utf8(out.stdout.clone())?;
     let lines: Vec<_> = stdout.split('\n').collect();
@@ -220,9 +221,2421 @@ fn test_2020() -> Result<()> {
 // --------------------------------------------------
 #[test]
 fn year() -> Result<()> {
-    let cmd = Command::cargo_bin(PRG)?.arg("-y").assert().success();
+    let cmd = calr()?.arg("-y").assert().success();
     let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
     let lines: Vec<&str> = stdout.split('\n').collect();
     assert_eq!(lines.len(), 37);
     Ok(())
 }
+
+// --------------------------------------------------
+#[test]
+fn ascii_box() -> Result<()> {
+    let cmd = calr()?
+        .args(["--ascii-box", "-m", "5", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert_eq!(lines[0], "┌───── May 2020 ─────┐  ");
+    assert_eq!(lines[8], "└────────────────────┘  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn ascii_box_ascii() -> Result<()> {
+    let cmd = calr()?
+        .args(["--ascii-box", "--ascii", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], format!("{:>35}", "2020"));
+    assert_eq!(
+        lines[1],
+        "+----- January ------+  +----- February -----+  +------ March -------+  "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn timezone() -> Result<()> {
+    calr()?
+        .args(["--timezone", "Asia/Tokyo", "-m", "4", "2020"])
+        .assert()
+        .success()
+        .stdout(fs::read_to_string("tests/expected/4-2020.txt")?);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_invalid_timezone() -> Result<()> {
+    let output = calr()?
+        .args(["--timezone", "Mars/Olympus"])
+        .output()
+        .expect("fail");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).expect("invalid UTF-8");
+    assert_eq!(stderr.trim(), r#"Invalid timezone "Mars/Olympus""#);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn count_weekday_month() -> Result<()> {
+    calr()?
+        .args(["--count-weekday", "fri", "-m", "3", "2024"])
+        .assert()
+        .success()
+        .stdout("Fridays in March 2024: 5\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn count_weekday_year() -> Result<()> {
+    let cmd = calr()?
+        .args(["--count-weekday", "mon", "2024"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "Mondays in January 2024: 5");
+    assert_eq!(lines[11], "Mondays in December 2024: 5");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn from_to() -> Result<()> {
+    let cmd = calr()?
+        .args(["--from", "2024-11", "--to", "2025-02"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 17);
+    assert_eq!(
+        lines[0],
+        "   November 2024         December 2024          January 2025      "
+    );
+    assert_eq!(lines[9], "   February 2025      ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_from_after_to() -> Result<()> {
+    let output = calr()?
+        .args(["--from", "2024-05", "--to", "2024-03"])
+        .output()
+        .expect("fail");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).expect("invalid UTF-8");
+    assert_eq!(
+        stderr.trim(),
+        r#"--to "2024-03" is earlier than --from "2024-05""#
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn summary() -> Result<()> {
+    calr()?
+        .args(["--from", "2024-03", "--to", "2024-05", "--summary"])
+        .assert()
+        .success()
+        .stdout("92 days (2024-03-01 to 2024-05-31)\n");
+
+    calr()?
+        .args(["-3", "-m", "1", "2024", "--summary"])
+        .assert()
+        .success()
+        .stdout("91 days (2023-12-01 to 2024-02-29)\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn compact() -> Result<()> {
+    // April 2020 has five week rows, so the padding line is dropped
+    let expected: String = fs::read_to_string("tests/expected/4-2020.txt")?
+        .lines()
+        .take(7)
+        .map(|line| format!("{line}\n"))
+        .collect();
+    calr()?
+        .args(["--compact", "-m", "4", "2020"])
+        .assert()
+        .success()
+        .stdout(expected);
+
+    // May 2020 ends with "31" on its own row, with no blank line after it
+    let cmd = calr()?
+        .args(["--compact", "-m", "5", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[7], "31                    ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn iso_week() -> Result<()> {
+    calr()?
+        .args(["--iso-week", "2024-02-26"])
+        .assert()
+        .success()
+        .stdout("2024-W09-1\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn iso_year_late_december() -> Result<()> {
+    // 2024-12-31はISO週では2025-W01
+    calr()?
+        .args(["--iso-year", "2024-12-31"])
+        .assert()
+        .success()
+        .stdout("2025\n");
+    calr()?
+        .args(["--iso-year", "2021-01-01"])
+        .assert()
+        .success()
+        .stdout("2020\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_invalid_iso_week() -> Result<()> {
+    let output = calr()?
+        .args(["--iso-week", "2024-13-01"])
+        .output()
+        .expect("fail");
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).expect("invalid UTF-8");
+    assert_eq!(stderr.trim(), r#"Invalid date "2024-13-01""#);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn rtl() -> Result<()> {
+    let cmd = calr()?
+        .args(["-3", "--rtl", "-m", "5", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[1],
+        "        June                  May                  April          "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn quarter() -> Result<()> {
+    let cmd = calr()?.args(["--quarter=2", "2024"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0].trim(), "Q2 2024");
+    assert_eq!(
+        lines[1],
+        "       April                  May                   June          "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_quarter_and_three() -> Result<()> {
+    let expected = "the argument '--quarter <QUARTER>' cannot be used with '--three'";
+    calr()?
+        .args(["--quarter", "1", "-3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(expected));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn agenda() -> Result<()> {
+    let cmd = calr()?
+        .args(["--agenda", "--highlight", "2024-03-04", "-m", "3", "2024"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 31);
+    assert_eq!(lines[0], "Fri 2024-03-01");
+    assert_eq!(lines[3], "Mon 2024-03-04 *");
+    assert_eq!(lines[30], "Sun 2024-03-31");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn highlight() -> Result<()> {
+    calr()?
+        .args(["--highlight", "2020-04-15,2020-04-30", "-m", "4", "2020"])
+        .args(["--color", "always"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "12 13 14 \u{1b}[7m15\u{1b}[0m 16 17 18  \n",
+        ))
+        .stdout(predicate::str::contains(
+            "26 27 28 29 \u{1b}[7m30\u{1b}[0m        \n",
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn fiscal_start() -> Result<()> {
+    let cmd = calr()?
+        .args(["--fiscal-start", "4", "2024"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "     April 2024             May 2024             June 2024        "
+    );
+    assert_eq!(
+        lines[27],
+        "    January 2025         February 2025           March 2025       "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn diff_years() -> Result<()> {
+    let cmd = calr()?
+        .args(["--diff=2020,2025", "-m", "2"])
+        .args(["--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "   February 2020         February 2025      ");
+    assert_eq!(
+        lines[6],
+        "23 24 25 26 27 28 \u{1b}[7m29\u{1b}[0m  23 24 25 26 27 28     "
+    );
+    assert_eq!(
+        lines[9],
+        "Highlighted days fall on a different weekday in 2020 and 2025"
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_diff_one_year() -> Result<()> {
+    calr()?
+        .args(["--diff", "2024"])
+        .assert()
+        .failure()
+        .stderr("--diff requires exactly two years\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn months_per_row() -> Result<()> {
+    let cmd = calr()?
+        .args(["--months-per-row", "4", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 27);
+    assert_eq!(lines[0], format!("{:>43}", "2020"));
+    assert_eq!(
+        lines[1].split_whitespace().collect::<Vec<_>>(),
+        ["January", "February", "March", "April"]
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn names_locale() -> Result<()> {
+    let cmd = calr()?.args(["names", "--locale", "fr"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 21);
+    assert_eq!(lines[0], "Months");
+    assert_eq!(lines[1], "  janvier");
+    assert_eq!(lines[13], "Weekdays");
+    assert_eq!(lines[14], "  dimanche");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn locale_month() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "fév", "2024", "--locale", "fr"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "    février 2024      ");
+    assert_eq!(lines[1], "di lu ma me je ve sa  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_invalid_locale() -> Result<()> {
+    calr()?
+        .args(["names", "--locale", "xx"])
+        .assert()
+        .failure()
+        .stderr("Invalid locale \"xx\"\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn first_day_auto() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--locale", "de", "--first-day", "auto"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Mo Di Mi Do Fr Sa So  ");
+    assert_eq!(lines[2], "             1  2  3  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn first_day_auto_without_locale() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--first-day", "auto"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn format_svg() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "--format",
+            "svg",
+            "-m",
+            "3",
+            "2024",
+            "--highlight",
+            "2024-03-10",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(stdout.starts_with("<svg "));
+    assert!(stdout.ends_with("</svg>\n"));
+    assert!(stdout.contains(r#"<text x="84" y="15" text-anchor="middle">March 2024</text>"#));
+    assert!(stdout.contains(r##"<rect x="0" y="80" width="24" height="20" fill="#cccccc"/>"##));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn highlight_range() -> Result<()> {
+    let cmd = calr()?
+        .args(["--from", "2024-03", "--to", "2024-04"])
+        .args(["--color", "always"])
+        .args(["--highlight-range", "2024-03-30:2024-04-01"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[3],
+        "                1  2     \u{1b}[7m 1\u{1b}[0m  2  3  4  5  6  "
+    );
+    assert_eq!(
+        lines[7],
+        "24 25 26 27 28 29 \u{1b}[7m30\u{1b}[0m  28 29 30              "
+    );
+    assert_eq!(
+        lines[8],
+        "\u{1b}[7m31\u{1b}[0m                                          "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn no_year() -> Result<()> {
+    let cmd = calr()?.args(["--no-year", "2020"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 35);
+    assert_eq!(
+        lines[0].split_whitespace().collect::<Vec<_>>(),
+        ["January", "February", "March"]
+    );
+    assert!(!stdout.contains("2020"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn week_across_months() -> Result<()> {
+    calr()?
+        .env("CALR_TODAY", "2024-04-30")
+        .args(["--week", "--first-day", "monday"])
+        .args(["--color", "always"])
+        .assert()
+        .success()
+        .stdout("Mo Tu We Th Fr Sa Su  \n29 \u{1b}[7m30\u{1b}[0m  1  2  3  4  5  \n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_invalid_calr_today() -> Result<()> {
+    calr()?
+        .env("CALR_TODAY", "2024-13-01")
+        .assert()
+        .failure()
+        .stderr("Invalid date \"2024-13-01\"\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn month_list() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "dec,jan,mar,jan", "2024"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], format!("{:>32}", "2024"));
+    assert_eq!(
+        lines[1].split_whitespace().collect::<Vec<_>>(),
+        ["January", "March", "December"]
+    );
+    assert_eq!(
+        lines[3],
+        "    1  2  3  4  5  6                  1  2   1  2  3  4  5  6  7  "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_month_list_and_three() -> Result<()> {
+    calr()?
+        .args(["-m", "1,3", "-3", "2024"])
+        .assert()
+        .failure()
+        .stderr("multiple months cannot be combined with -3, --summary or --diff\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn format_ndjson() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-04")
+        .args(["--format", "ndjson", "--from", "2024-02", "--to", "2024-03"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 60);
+    assert_eq!(
+        lines[0],
+        r#"{"date":"2024-02-01","weekday":"Thu","today":false,"day_of_year":32,"iso_week":5}"#
+    );
+    assert_eq!(
+        lines[32],
+        r#"{"date":"2024-03-04","weekday":"Mon","today":true,"day_of_year":64,"iso_week":10}"#
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn events() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-04")
+        .args(["-m", "3", "2024", "--events", "tests/inputs/events.txt"])
+        .args(["--color", "always"])
+        .assert()
+        .success()
+        .stderr("tests/inputs/events.txt:4: Invalid event \"not a date\"\n");
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 当日は反転表示、イベントは下線で区別する
+    assert_eq!(lines[3], " 3 \u{1b}[4;7m 4\u{1b}[0m  5  6  7  8  9  ");
+    assert_eq!(lines[5], "17 18 19 \u{1b}[4m20\u{1b}[0m 21 22 23  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_missing_events_file() -> Result<()> {
+    calr()?
+        .args(["--events", "tests/inputs/no-such-file.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with(
+            "tests/inputs/no-such-file.txt: ",
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn separator() -> Result<()> {
+    let cmd = calr()?
+        .args(["--separator", "|", "-m", "4", "-3", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], format!("{:>31}", "2020"));
+    assert_eq!(
+        lines[2],
+        "Su Mo Tu We Th Fr Sa|Su Mo Tu We Th Fr Sa|Su Mo Tu We Th Fr Sa"
+    );
+    assert_eq!(
+        lines[3],
+        " 1  2  3  4  5  6  7|          1  2  3  4|                1  2"
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn separator_single_month() -> Result<()> {
+    let cmd = calr()?
+        .args(["--separator", "|", "-m", "4", "2020"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "     April 2020     ");
+    assert_eq!(lines[2], "          1  2  3  4");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn weeks_count() -> Result<()> {
+    calr()?
+        .args(["--weeks-count", "-m", "2", "2026"])
+        .assert()
+        .success()
+        .stdout("4\n");
+    calr()?
+        .args(["--weeks-count", "-m", "3", "2024"])
+        .assert()
+        .success()
+        .stdout("6\n");
+    calr()?
+        .args(["--weeks-count", "-m", "3", "2024", "--first-day", "monday"])
+        .assert()
+        .success()
+        .stdout("5\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn calendar_persian() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-21")
+        .args(["--calendar", "persian"])
+        .args(["--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "   Farvardin 1403     ");
+    assert_eq!(lines[2], "          1 \u{1b}[7m 2\u{1b}[0m  3  4  ");
+    assert_eq!(lines[6], "26 27 28 29 30 31     ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn calendar_persian_year() -> Result<()> {
+    let cmd = calr()?
+        .args(["--calendar", "persian", "1403"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 36);
+    assert_eq!(lines[0], format!("{:>32}", "1403"));
+    assert_eq!(
+        lines[28].split_whitespace().collect::<Vec<_>>(),
+        ["Dey", "Bahman", "Esfand"]
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn era_japanese() -> Result<()> {
+    let cmd = calr()?
+        .args(["--era", "japanese", "-m", "5", "2019"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "    May 令和元年      ");
+
+    let cmd = calr()?
+        .args(["--era", "japanese", "2019"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], format!("{}平成31年/令和元年", " ".repeat(21)));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn debug_columns() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "--debug-columns",
+            "-m",
+            "3",
+            "2024",
+            "--first-day",
+            "monday",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "   Mo Tu We Th Fr Sa Su  ");
+    assert_eq!(lines[2], " 5              1  2  3  ");
+    assert_eq!(lines[3], " 1  4  5  6  7  8  9 10  ");
+    assert!(lines.iter().all(|line| line.len() == 25));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn list_months() -> Result<()> {
+    calr()?
+        .args(["--list-months", "-3", "-m", "3", "2024"])
+        .assert()
+        .success()
+        .stdout("2024-02\n2024-03\n2024-04\n");
+    calr()?
+        .args(["--list-months", "--fiscal-start", "11", "2024"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("2024-11\n2024-12\n2025-01\n"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn theme_mono() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-04")
+        .args(["--theme", "mono", "-3", "-m", "3", "2024"])
+        .args(["--color", "always"])
+        .args(["--events", "tests/inputs/events.txt"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    // 色の指定(前景色・背景色)を含まない
+    assert!(stdout.contains("\u{1b}["));
+    assert!(!stdout.contains("38;"));
+    assert!(!stdout.contains("48;"));
+    for code in 30..=49 {
+        assert!(!stdout.contains(&format!("[{code}m")));
+        assert!(!stdout.contains(&format!(";{code}m")));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn color_never() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-04")
+        .args([
+            "--theme",
+            "solarized",
+            "--color",
+            "never",
+            "-m",
+            "3",
+            "2024",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains('\u{1b}'));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn around() -> Result<()> {
+    let cmd = calr()?
+        .args(["--around", "2024-03-15"])
+        .args(["--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "     March 2024       ");
+    assert_eq!(lines[4], "10 11 12 13 14 \u{1b}[7m15\u{1b}[0m 16  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_around_and_month() -> Result<()> {
+    calr()?
+        .args(["--around", "2024-03-15", "-m", "4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn multiple_years() -> Result<()> {
+    let cmd = calr()?.args(["2023", "2024"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0].trim(), "2023");
+    assert_eq!(lines[36], "");
+    assert_eq!(lines[37].trim(), "2024");
+    assert_eq!(stdout.matches("January").count(), 2);
+    assert_eq!(stdout.matches("December").count(), 2);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn years_range() -> Result<()> {
+    let cmd = calr()?.args(["--years", "2023-2025"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.matches("January").count(), 3);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_bad_years_range() -> Result<()> {
+    calr()?
+        .args(["--years", "2025-2023"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            r#"Invalid year range "2025-2023""#,
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn december_9999() -> Result<()> {
+    let cmd = calr()?.args(["-m", "dec", "9999"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0].trim(), "December 9999");
+    assert!(lines[6].starts_with("26 27 28 29 30 31"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn positional_month_year() -> Result<()> {
+    let cmd = calr()?.args(["3", "2024"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "     March 2024       ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn positional_year_only() -> Result<()> {
+    let cmd = calr()?.args(["2024"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.lines().next().map(str::trim), Some("2024"));
+    assert_eq!(stdout.matches("March").count(), 1);
+    assert_eq!(stdout.matches("December").count(), 1);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn positional_single_12_is_year() -> Result<()> {
+    let cmd = calr()?.args(["12"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.lines().next().map(str::trim), Some("12"));
+    assert_eq!(stdout.matches("January").count(), 1);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn grid_lines() -> Result<()> {
+    let cmd = calr()?
+        .args(["2024", "--months-per-row", "1", "--grid-lines"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[9], "-".repeat(20));
+    assert_eq!(lines[10].trim(), "February");
+    assert_eq!(stdout.matches(&"-".repeat(20)).count(), 11);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn output_file() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("calr-output-{}.txt", std::process::id()));
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--output"])
+        .arg(&path)
+        .assert()
+        .success();
+    assert!(cmd.get_output().stdout.is_empty());
+    let content = fs::read_to_string(&path)?;
+    fs::remove_file(&path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "     March 2024       ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_output_bad_path() -> Result<()> {
+    calr()?
+        .args(["--output", "tests/inputs/missing/out.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("tests/inputs/missing/out.txt: "));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn split_output() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("calr-split-{}", std::process::id()));
+    let cmd = calr()?
+        .args(["--from", "2024-11", "--to", "2025-01", "--split-output"])
+        .arg(&dir)
+        .assert()
+        .success();
+    assert!(cmd.get_output().stdout.is_empty());
+    let contents: Vec<String> = ["2024-11", "2024-12", "2025-01"]
+        .iter()
+        .map(|name| fs::read_to_string(dir.join(format!("{name}.txt"))))
+        .collect::<Result<_, _>>()?;
+    let files = fs::read_dir(&dir)?.count();
+    fs::remove_dir_all(&dir)?;
+    assert_eq!(files, 3);
+    assert!(contents[0].starts_with("   November 2024      \n"));
+    assert_eq!(contents[1].lines().nth(6), Some("29 30 31              "));
+    assert!(contents[2].starts_with("    January 2025      \n"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn today_style() -> Result<()> {
+    for (style, expected) in [
+        ("reverse", "10 11 12 \u{1b}[7m13\u{1b}[0m 14 15 16  "),
+        ("bracket", "10 11 12[13]14 15 16  "),
+        ("underline", "10 11 12 \u{1b}[4m13\u{1b}[0m 14 15 16  "),
+    ] {
+        let cmd = calr()?
+            .env("CALR_TODAY", "2024-03-13")
+            .args(["--today-style", style])
+            .args(["--color", "always"])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[4], expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn today_style_bracket_row_edges() -> Result<()> {
+    // 行末の2桁の日付は閉じ括弧を月同士の区切りに置き、行頭の2桁の日付は開き括弧を省く
+    for (today, args, line, expected) in [
+        ("2024-03-16", vec![], 4, "10 11 12 13 14 15[16] "),
+        ("2024-03-10", vec![], 4, "10]11 12 13 14 15 16  "),
+        (
+            "2024-03-16",
+            vec!["-3"],
+            5,
+            "11 12 13 14 15 16 17  10 11 12 13 14 15[16] 14 15 16 17 18 19 20  ",
+        ),
+        (
+            "2024-03-10",
+            vec!["-3"],
+            5,
+            "11 12 13 14 15 16 17  10]11 12 13 14 15 16  14 15 16 17 18 19 20  ",
+        ),
+    ] {
+        let cmd = calr()?
+            .env("CALR_TODAY", today)
+            .args(["--today-style", "bracket"])
+            .args(args)
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[line], expected);
+        assert!(lines.iter().all(|line| line.len() <= lines[1].len()));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn show_leap() -> Result<()> {
+    for (year, expected) in [("2020", "2020 (leap year)"), ("2021", "2021")] {
+        let cmd = calr()?.args([year, "--show-leap"]).assert().success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        assert_eq!(stdout.lines().next().map(str::trim), Some(expected));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn year_progress() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-04-07")
+        .args(["-y", "--year-progress"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.lines().last(), Some("Day 98 of 366 (26.8%)"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn year_progress_other_year() -> Result<()> {
+    // 当日の年以外の年では進捗を出力しない
+    let expected = calr()?
+        .env("CALR_TODAY", "2024-04-07")
+        .arg("2020")
+        .assert()
+        .success();
+    let expected = String::from_utf8(expected.get_output().stdout.clone())?;
+    calr()?
+        .env("CALR_TODAY", "2024-04-07")
+        .args(["2020", "--year-progress"])
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn invert() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--invert"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "Su Mo Tu We Th Fr Sa  ");
+    assert_eq!(lines[7], "     March 2024       ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn iso() -> Result<()> {
+    let cmd = calr()?
+        .args(["--iso", "-m", "1", "2021"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "   Mo Tu We Th Fr Sa Su  ");
+    assert_eq!(lines[2], "53              1  2  3  ");
+    assert_eq!(lines[3], " 1  4  5  6  7  8  9 10  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_iso_and_first_day() -> Result<()> {
+    calr()?
+        .args(["--iso", "--first-day", "sun"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn link_format_off_without_tty() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "-m",
+            "3",
+            "2024",
+            "--link-format",
+            "https://example.com/{date}",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains("\u{1b}]8;;"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn from_day() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--from-day", "15"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[2].trim(), "");
+    assert_eq!(lines[3].trim(), "");
+    assert_eq!(lines[4], "               15 16  ");
+    assert_eq!(lines[5], "17 18 19 20 21 22 23  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_from_day_with_year() -> Result<()> {
+    calr()?
+        .args(["2024", "--from-day", "15"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--from-day requires a single month",
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn between() -> Result<()> {
+    calr()?
+        .args(["--between", "2024-01-01", "2024-12-31"])
+        .assert()
+        .success()
+        .stdout("2024-01-01 to 2024-12-31: 366 days inclusive, 365 days exclusive\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn between_reversed() -> Result<()> {
+    calr()?
+        .args(["--between", "2023-03-01", "2023-02-01"])
+        .assert()
+        .success()
+        .stdout("2023-02-01 to 2023-03-01: 29 days inclusive, 28 days exclusive (reversed)\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn between_one_day() -> Result<()> {
+    calr()?
+        .args(["--between", "2024-01-01", "2024-01-02"])
+        .assert()
+        .success()
+        .stdout("2024-01-01 to 2024-01-02: 2 days inclusive, 1 day exclusive\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn months_between_same_month() -> Result<()> {
+    calr()?
+        .args(["--months-between", "2024-03", "2024-03"])
+        .assert()
+        .success()
+        .stdout("2024-03 to 2024-03: 1 month\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn months_between_cross_year() -> Result<()> {
+    calr()?
+        .args(["--months-between", "2025-02", "2023-11"])
+        .assert()
+        .success()
+        .stdout("2023-11 to 2025-02: 16 months (reversed)\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn months_between_zero_pad_year() -> Result<()> {
+    calr()?
+        .args(["--zero-pad-year", "--months-between", "0044-01", "0044-03"])
+        .assert()
+        .success()
+        .stdout("0044-01 to 0044-03: 3 months\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_bad_between_date() -> Result<()> {
+    calr()?
+        .args(["--between", "2024-02-30", "2024-03-01"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid date "2024-02-30""#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn format_heatmap() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-01-03")
+        .args(["2024", "--format", "heatmap"])
+        .args(["--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert!(lines[0].starts_with("   Jan Feb Mar"));
+    assert!(lines[4].starts_with("We \u{1b}[7m@\u{1b}[0m..."));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn quiet() -> Result<()> {
+    calr()?
+        .args(["--quiet", "-m", "3", "2024"])
+        .assert()
+        .success()
+        .stdout("");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_quiet_bad_month() -> Result<()> {
+    calr()?
+        .args(["--quiet", "-m", "foo", "2024"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains(r#"Invalid month "foo""#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn piped_output_has_no_highlight() -> Result<()> {
+    let cmd = calr()?.env("CALR_TODAY", "2024-03-13").assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains('\u{1b}'));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn no_highlight_today() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-13")
+        .args(["-h", "--color", "always", "--highlight", "2024-03-14"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[4], "10 11 12 13 \u{1b}[7m14\u{1b}[0m 15 16  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn help_long_flag() -> Result<()> {
+    calr()?
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--no-highlight-today"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn roman() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "4", "2024", "--roman"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.lines().next(), Some("      IV 2024         "));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn header_gap() -> Result<()> {
+    let cmd = calr()?
+        .args(["-3", "-m", "6", "2024", "--header-gap"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[3].trim(), "");
+    assert_eq!(
+        lines[4],
+        "          1  2  3  4                     1      1  2  3  4  5  6  "
+    );
+    assert_eq!(
+        lines[9],
+        "                      30                                          "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn min_width() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "9", "2024", "--locale", "es", "--min-width", "24"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "    septiembre 2024       ");
+    assert_eq!(lines[2], " 1  2  3  4  5  6  7      ");
+    assert!(lines.iter().all(|line| line.chars().count() == 26));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn parse_single_digits() -> Result<()> {
+    calr()?
+        .args(["parse", "2024-3-4"])
+        .assert()
+        .success()
+        .stdout("2024-03-04\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn parse_slashes() -> Result<()> {
+    calr()?
+        .args(["parse", "2024/12/1"])
+        .assert()
+        .success()
+        .stdout("2024-12-01\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_parse_invalid_date() -> Result<()> {
+    calr()?
+        .args(["parse", "2023-2-29"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid date "2023-2-29""#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn week_of_month() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--week-of-month"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[2], " 1                 1  2  ");
+    assert_eq!(lines[3], " 2  3  4  5  6  7  8  9  ");
+    assert_eq!(lines[7], " 6 31                    ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn format_tsv() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "-m",
+            "mar,apr",
+            "2024",
+            "--format",
+            "tsv",
+            "--first-day",
+            "mon",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "year\tmonth\tMo\tTu\tWe\tTh\tFr\tSa\tSu");
+    assert_eq!(lines[1], "2024\t3\t\t\t\t\t1\t2\t3");
+    assert_eq!(lines[5], "2024\t3\t25\t26\t27\t28\t29\t30\t31");
+    assert_eq!(lines[6], "year\tmonth\tMo\tTu\tWe\tTh\tFr\tSa\tSu");
+    assert_eq!(lines[7], "2024\t4\t1\t2\t3\t4\t5\t6\t7");
+    assert!(lines.iter().all(|line| line.matches('\t').count() == 8));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn mark_boundaries() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--mark-boundaries", "--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[2], "               \u{1b}[4m 1\u{1b}[0m  2  ");
+    assert_eq!(lines[3], " 3  4  5  6  7  8  9  ");
+    assert_eq!(lines[7], "\u{1b}[4m31\u{1b}[0m                    ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn repeat() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--repeat", "5"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert_eq!(stdout.matches("March").count(), 5);
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 3ヶ月の行と2ヶ月の行(末尾の行も省略されない)
+    assert_eq!(lines[1].matches("March").count(), 3);
+    assert_eq!(lines[10].matches("March").count(), 2);
+    assert_eq!(lines[17], "31                    31                    ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn repeat_requires_single_month() -> Result<()> {
+    calr()?
+        .args(["2024", "--repeat", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--repeat requires a single month"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn digits_arabic() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--digits", "arabic"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "     March 2024       ");
+    assert_eq!(lines[3], " ٣  ٤  ٥  ٦  ٧  ٨  ٩  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn strict_rejects_prefix() -> Result<()> {
+    calr()?
+        .args(["-m", "f", "2024", "--strict"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid month "f""#));
+    calr()?
+        .args(["-m", "February", "2024", "--strict"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("February 2024"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dual_persian() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--dual", "persian"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "     March 2024           Esfand 1402       ");
+    assert_eq!(lines[2], "                1  2         1  2  3  4  5  ");
+    // 2つの月の行数は同じ(8行と末尾の空行)
+    assert_eq!(lines.len(), 9);
+    assert!(lines[..8].iter().all(|line| line.chars().count() == 44));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn rolling_year() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "5", "2024", "--rolling-year"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "      May 2024             June 2024             July 2024        "
+    );
+    assert_eq!(
+        lines[27],
+        "   February 2025           March 2025            April 2025       "
+    );
+    assert_eq!(lines.len(), 35);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn collapse_weekends() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--collapse-weekends"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Mo Tu We Th Fr  ");
+    assert_eq!(lines[2], "             1  ");
+    assert_eq!(lines[3], " 4  5  6  7  8  ");
+    // 週末(2日・3日)は表示しない
+    assert!(!stdout.contains(" 2 ") && !stdout.contains(" 3 "));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn business_days() -> Result<()> {
+    calr()?
+        .args(["-m", "3", "2024", "--business-days"])
+        .assert()
+        .success()
+        .stdout("21\n");
+    // イベントの日付(3月4日・3月20日)を休日として除く
+    calr()?
+        .args(["-m", "3", "2024", "--business-days"])
+        .args(["--events", "tests/inputs/events.txt"])
+        .assert()
+        .success()
+        .stdout("19\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn zero_pad_year() -> Result<()> {
+    calr()?
+        .args(["-m", "3", "44", "--zero-pad-year"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("     March 0044       \n"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn moon() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--moon"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "     March 2024          ");
+    assert_eq!(lines[5], "17 18 19 20 21 22 23 🌔  ");
+    assert_eq!(lines[6], "24 25 26 27 28 29 30 🌖  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn day_separator() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--day-separator", "·"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa  ");
+    assert_eq!(lines[2], "                1· 2  ");
+    assert_eq!(lines[3], " 3· 4· 5· 6· 7· 8· 9  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_empty_day_separator() -> Result<()> {
+    calr()?
+        .args(["--day-separator", ""])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid day separator """#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn peek_month_boundary() -> Result<()> {
+    calr()?
+        .env("CALR_TODAY", "2024-04-30")
+        .args(["--peek", "--first-day", "monday"])
+        .args(["--color", "always"])
+        .assert()
+        .success()
+        .stdout("Mo Tu We Th Fr Sa Su  \n29 \u{1b}[7m30\u{1b}[0m  \n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn style_categories() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--style", "weekends=blue,events=green"])
+        .args(["--events", "tests/inputs/events.txt", "--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[3],
+        "\u{1b}[34m 3\u{1b}[0m \u{1b}[4;32m 4\u{1b}[0m  5  6  7  8 \u{1b}[34m 9\u{1b}[0m  "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn style_honors_color_never() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "-m",
+            "3",
+            "2024",
+            "--style",
+            "weekends=blue",
+            "--color",
+            "never",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains('\u{1b}'));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_invalid_style() -> Result<()> {
+    calr()?
+        .args(["--style", "today=pink"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid color "pink""#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn frame_today_month() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-05-10")
+        .args(["2024", "--frame-today-month"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 5月のみ枠線で囲み、他の月は同じ大きさの空白で囲む
+    assert_eq!(stdout.matches('┌').count(), 1);
+    assert_eq!(stdout.matches('└').count(), 1);
+    assert!(lines[11].contains("┌─────── May ────────┐"));
+    assert_eq!(
+        lines[1],
+        "       January                 February                 March           "
+    );
+    assert!(lines[12].starts_with(" Su Mo Tu We Th Fr Sa   │Su Mo Tu We Th Fr Sa│"));
+    assert_eq!(lines.len(), 40);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn indent() -> Result<()> {
+    let plain = calr()?.args(["-m", "3", "2024"]).assert().success();
+    let plain = String::from_utf8(plain.get_output().stdout.clone())?;
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--indent", "4"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    // 空行以外の各行の先頭に4桁の空白を付与
+    let expected: Vec<String> = plain
+        .lines()
+        .map(|line| match line {
+            "" => String::new(),
+            _ => format!("    {line}"),
+        })
+        .collect();
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), expected);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn weekday_of_date() -> Result<()> {
+    calr()?
+        .args(["weekday", "2024-03-04"])
+        .assert()
+        .success()
+        .stdout("Monday\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn weekday_of_date_locale() -> Result<()> {
+    calr()?
+        .args(["--locale", "de", "weekday", "2024-03-10"])
+        .assert()
+        .success()
+        .stdout("Sonntag\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_weekday_invalid_date() -> Result<()> {
+    calr()?
+        .args(["weekday", "2024-02-30"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#"Invalid date "2024-02-30""#));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn continuous_days() -> Result<()> {
+    let cmd = calr()?
+        .args(["--from", "2024-03", "--to", "2024-04", "--continuous-days"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 4月1日は3月1日から数えて32日目
+    assert_eq!(lines[3], "                1  2     32 33 34 35 36 37  ");
+    assert_eq!(lines[7], "24 25 26 27 28 29 30  59 60 61              ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dim_adjacent() -> Result<()> {
+    let cmd = calr()?
+        .args([
+            "-3",
+            "-m",
+            "3",
+            "2024",
+            "--dim-adjacent",
+            "--color",
+            "always",
+        ])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 前後の月(2月・4月)のみ薄く表示し、中央の月(3月)は通常の表示
+    assert_eq!(
+        lines[1],
+        "\u{1b}[2m      February      \u{1b}[0m         March          \u{1b}[2m       April        \u{1b}[0m  "
+    );
+    assert_eq!(
+        lines[7],
+        "\u{1b}[2m25 26 27 28 29      \u{1b}[0m  24 25 26 27 28 29 30  \u{1b}[2m28 29 30            \u{1b}[0m  "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dim_adjacent_color_never() -> Result<()> {
+    let cmd = calr()?
+        .args(["-3", "--dim-adjacent", "--color", "never"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains('\u{1b}'));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn saturday_first() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "3", "2024", "--saturday"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Sa Su Mo Tu We Th Fr  ");
+    assert_eq!(lines[2], "                   1  ");
+    assert_eq!(lines[7], "30 31                 ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn reverse_months() -> Result<()> {
+    let cmd = calr()?
+        .args(["2024", "--reverse-months"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[1].trim_start().starts_with("December"));
+    assert!(lines[28].trim_end().ends_with("January"));
+    // 最後の段の右端が1月(2024年1月1日は月曜日)
+    assert!(lines[30].ends_with("    1  2  3  4  5  6  "));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn config_defaults() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_CONFIG", "tests/inputs/config.toml")
+        .args(["-m", "3", "2024"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            r#"config.toml:3: Invalid config "locale = ja""#,
+        ));
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Mo Tu We Th Fr Sa Su  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn config_overridden_by_flag() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_CONFIG", "tests/inputs/config.toml")
+        .args(["-m", "3", "2024", "--first-day", "sunday"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa  ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn week_rule() -> Result<()> {
+    // 2022年1月2日〜8日の週
+    for (rule, expected) in [
+        ("iso", "52  2  3  4  5  6  7  8  "),
+        ("jan1", " 2  2  3  4  5  6  7  8  "),
+        ("first-full", " 1  2  3  4  5  6  7  8  "),
+    ] {
+        let cmd = calr()?
+            .args(["-m", "1", "2022", "--week-rule", rule])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[3], expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn print_today() -> Result<()> {
+    calr()?
+        .env("CALR_TODAY", "2024-03-04")
+        .args(["--print-today", "--timezone", "Asia/Tokyo"])
+        .assert()
+        .success()
+        .stdout("2024-03-04\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn week_epoch() -> Result<()> {
+    let cmd = calr()?
+        .args(["-m", "4", "2024", "--week-epoch", "2024-01-01"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 3ヶ月後の週は第13週
+    assert_eq!(lines[2], "13     1  2  3  4  5  6  ");
+    assert_eq!(lines[6], "17 28 29 30              ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn weekends() -> Result<()> {
+    calr()?
+        .args(["-m", "3", "2024", "--weekends"])
+        .assert()
+        .success()
+        .stdout("Weekend days in March 2024: 10\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn weekends_year() -> Result<()> {
+    let cmd = calr()?.args(["2024", "--weekends"]).assert().success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 13);
+    assert_eq!(lines[1], "Weekend days in February 2024: 8");
+    assert_eq!(lines[12], "Total: 104");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn highlight_week() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-13")
+        .args(["-3", "--highlight-week", "--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    // 3月10日〜16日の7日分を全て反転表示
+    let week: Vec<String> = (10..=16)
+        .map(|day| format!("\u{1b}[7m{day}\u{1b}[0m"))
+        .collect();
+    assert_eq!(
+        lines[5],
+        format!(
+            "11 12 13 14 15 16 17  {}  14 15 16 17 18 19 20  ",
+            week.join(" ")
+        )
+    );
+    assert_eq!(
+        lines[4],
+        " 4  5  6  7  8  9 10   3  4  5  6  7  8  9   7  8  9 10 11 12 13  "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn highlight_week_color_never() -> Result<()> {
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-13")
+        .args(["-3", "--highlight-week", "--color", "never"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    assert!(!stdout.contains('\u{1b}'));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn header_align() -> Result<()> {
+    for (align, expected) in [
+        ("left", "March 2024            "),
+        ("center", "     March 2024       "),
+        ("right", "          March 2024  "),
+    ] {
+        let cmd = calr()?
+            .args(["-m", "3", "2024", "--header-align", align])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[0], expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn checksum() -> Result<()> {
+    // 装飾の有無に関わらず同じハッシュ値(装飾しない出力のハッシュ値)
+    for color in ["always", "never"] {
+        calr()?
+            .env("CALR_TODAY", "2024-03-13")
+            .args(["2024", "--checksum", "--color", color])
+            .assert()
+            .success()
+            .stdout("edb04a6bd04b373443f6adcd961f91dace55c47edac1602b54a53d9befeaf978\n");
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn six_week_rows() -> Result<()> {
+    // 4週で収まる2015年2月も、6週にまたがる2024年3月も6行の週で出力する
+    for month in ["2015-02", "2024-03"] {
+        let cmd = calr()?
+            .args(["--from", month, "--to", month])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[1].starts_with("Su Mo Tu We Th Fr Sa"));
+        assert!(lines[2..].iter().all(|line| line.len() == 22));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn locale_number() -> Result<()> {
+    for (locale, expected) in [
+        ("en", "Day 98 of 366 (26.8%)"),
+        ("de", "Day 98 of 366 (26,8%)"),
+    ] {
+        let cmd = calr()?
+            .env("CALR_TODAY", "2024-04-07")
+            .args([
+                "2024",
+                "--year-progress",
+                "--locale",
+                locale,
+                "--locale-number",
+            ])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        assert_eq!(stdout.lines().last(), Some(expected));
+    }
+    calr()?
+        .args(["--between", "2020-01-01", "2024-06-01"])
+        .args(["--locale", "de", "--locale-number"])
+        .assert()
+        .success()
+        .stdout("2020-01-01 to 2024-06-01: 1.614 days inclusive, 1.613 days exclusive\n");
+    calr()?
+        .args(["--months-between", "2024-01", "3224-06"])
+        .args(["--locale", "de", "--locale-number"])
+        .assert()
+        .success()
+        .stdout("2024-01 to 3224-06: 14.406 months\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn interactive_without_terminal() -> Result<()> {
+    // 「tui」フィーチャーなし、または端末以外からの実行ではエラー
+    calr()?
+        .arg("--interactive")
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with("--interactive requires"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn style_truecolor() -> Result<()> {
+    for (args, expected) in [
+        (
+            vec!["--truecolor"],
+            "                1 \u{1b}[38;2;38;139;210m 2\u{1b}[0m  ",
+        ),
+        (vec![], "                1 \u{1b}[36m 2\u{1b}[0m  "),
+    ] {
+        let cmd = calr()?
+            .args([
+                "-m",
+                "3",
+                "2024",
+                "--color",
+                "always",
+                "--style",
+                "weekends=#268bd2",
+            ])
+            .args(args)
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[2], expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn no_weekday_header() -> Result<()> {
+    let cmd = calr()?
+        .args(["-3", "-m", "3", "2024", "--no-weekday-header"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines.iter().all(|line| !line.contains("Su Mo")));
+    // 年ヘッダ + 月ヘッダ + 6週 + 空行
+    assert_eq!(lines.len(), 9);
+    assert_eq!(
+        lines[1],
+        "      February               March                 April          "
+    );
+    assert_eq!(
+        lines[2],
+        "             1  2  3                  1  2      1  2  3  4  5  6  "
+    );
+    assert!(lines[1..8].iter().all(|line| line.len() == lines[1].len()));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn recur() -> Result<()> {
+    // 2024年4月の月曜日は1・8・15・22・29日(当日は範囲外)
+    let cmd = calr()?
+        .env("CALR_TODAY", "2024-03-13")
+        .args(["-m", "4", "2024", "--recur", "mon", "--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    for day in ["1", "8", "15", "22", "29"] {
+        assert!(stdout.contains(&format!("\u{1b}[1m{day:>2}\u{1b}[0m")));
+    }
+    assert_eq!(stdout.matches("\u{1b}[1m").count(), 5);
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn validate_only() -> Result<()> {
+    calr()?
+        .args(["--validate-only", "--from", "2024-03", "--to", "2024-06"])
+        .assert()
+        .success()
+        .stdout("");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_validate_only_reversed_range() -> Result<()> {
+    calr()?
+        .args(["--validate-only", "--from", "2024-06", "--to", "2024-03"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            r#"--to "2024-03" is earlier than --from "2024-06""#,
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn narrow_columns() -> Result<()> {
+    // 1ヶ月分(22桁)しか収まらない幅では1行に1ヶ月ずつ並べる
+    let cmd = calr()?
+        .env("COLUMNS", "30")
+        .args(["-3", "-m", "3", "2024"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines.iter().all(|line| line.chars().count() <= 30));
+    assert_eq!(lines[1], "      February        ");
+    assert_eq!(lines[10], "       March          ");
+    assert_eq!(lines[19], "       April          ");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn peek_no_weekday_header() -> Result<()> {
+    // 1週目(2024-06-01)と6週目(2024-03-31)の行を表示する
+    for (today, expected) in [
+        (
+            "2024-06-01",
+            "Su Mo Tu We Th Fr Sa  \n                  \u{1b}[7m 1\u{1b}[0m  \n",
+        ),
+        (
+            "2024-03-31",
+            "Su Mo Tu We Th Fr Sa  \n\u{1b}[7m31\u{1b}[0m  \n",
+        ),
+    ] {
+        calr()?
+            .env("CALR_TODAY", today)
+            .args(["--peek", "--no-weekday-header", "--color", "always"])
+            .assert()
+            .success()
+            .stdout(expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn validate_only_interactive() -> Result<()> {
+    // 「tui」フィーチャーなしでは実際の実行と同じくエラー
+    let assert = calr()?.args(["--validate-only", "--interactive"]).assert();
+    if cfg!(all(feature = "tui", unix)) {
+        assert.success().stdout("");
+    } else {
+        assert.failure().stderr(predicate::str::contains(
+            "--interactive requires calr to be built with the tui feature",
+        ));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_validate_only_split_output_heatmap() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("calr-validate-{}", std::process::id()));
+    calr()?
+        .args(["--validate-only", "--format", "heatmap", "--split-output"])
+        .arg(&dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--split-output does not support --format heatmap",
+        ));
+    assert!(!dir.exists());
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn narrow_columns_indent() -> Result<()> {
+    // 字下げの8桁を除いた58桁には2ヶ月分まで収まる
+    let cmd = calr()?
+        .env("COLUMNS", "66")
+        .args(["-3", "-m", "3", "2024", "--indent", "8"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines.iter().all(|line| line.chars().count() <= 66));
+    assert_eq!(
+        lines[1],
+        "              February               March          "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn checksum_ignores_columns() -> Result<()> {
+    calr()?
+        .env("COLUMNS", "30")
+        .args(["2024", "--checksum"])
+        .assert()
+        .success()
+        .stdout("edb04a6bd04b373443f6adcd961f91dace55c47edac1602b54a53d9befeaf978\n");
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn astronomical_year() -> Result<()> {
+    // 0年は紀元前1年、-1年は紀元前2年(日付の並びはどちらも同じ)
+    for (args, expected) in [
+        (vec!["--around", "0000-03-15"], "     March 1 BC       "),
+        (vec!["--around=-0001-03-15"], "     March 2 BC       "),
+        (
+            vec!["--around", "0000-03-15", "--astronomical-year"],
+            "      March 0         ",
+        ),
+        (
+            vec!["--around=-0001-03-15", "--astronomical-year"],
+            "      March -1        ",
+        ),
+    ] {
+        let cmd = calr()?.args(args).assert().success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(lines[0], expected);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn config_conflicting_key() -> Result<()> {
+    // 両立しない「quiet」のみ除き、「first-day」は適用する
+    let expected = calr()?
+        .args(["-m", "3", "2024", "--first-day", "monday", "--checksum"])
+        .assert()
+        .success();
+    let expected = String::from_utf8(expected.get_output().stdout.clone())?;
+    calr()?
+        .env("CALR_CONFIG", "tests/inputs/config_conflict.toml")
+        .args(["-m", "3", "2024", "--checksum"])
+        .assert()
+        .success()
+        .stdout(expected)
+        .stderr(predicate::str::contains(
+            r#"tests/inputs/config_conflict.toml: Ignored config key "quiet""#,
+        ));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn header_align_ascii_box() -> Result<()> {
+    for (align, expected) in [
+        ("left", "┌─ March 2024 ───────┐  "),
+        ("right", "┌─────── March 2024 ─┐  "),
+    ] {
+        let cmd = calr()?
+            .args(["-m", "3", "2024", "--ascii-box", "--header-align", align])
+            .assert()
+            .success();
+        let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+        assert_eq!(stdout.lines().next(), Some(expected));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn diff_years_with_highlight() -> Result<()> {
+    // 曜日の異なる日付(2020-02-29)に加えて「--highlight」の日付も強調表示する
+    let cmd = calr()?
+        .args(["--diff=2020,2025", "-m", "2", "--highlight", "2020-02-10"])
+        .args(["--color", "always"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(cmd.get_output().stdout.clone())?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[4],
+        " 9 \u{1b}[7m10\u{1b}[0m 11 12 13 14 15   9 10 11 12 13 14 15  "
+    );
+    assert_eq!(
+        lines[6],
+        "23 24 25 26 27 28 \u{1b}[7m29\u{1b}[0m  23 24 25 26 27 28     "
+    );
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+#[cfg(target_os = "linux")]
+fn split_output_from_terminal() -> Result<()> {
+    // 疑似端末(「script」)から実行しても、ファイルには装飾・ハイパーリンクを書き込まない
+    if std::process::Command::new("script")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        return Ok(());
+    }
+    let dir = std::env::temp_dir().join(format!("calr-split-tty-{}", std::process::id()));
+    let command = format!(
+        "{} -m 3 2024 --split-output {} --link-format 'https://example.com/{{date}}'",
+        assert_cmd::cargo::cargo_bin(PRG).display(),
+        dir.display()
+    );
+    let status = std::process::Command::new("script")
+        .args(["-qc", &command, "/dev/null"])
+        .env("CALR_TODAY", "2024-03-13")
+        .env("CALR_CONFIG", "tests/inputs/empty.toml")
+        .output()?
+        .status;
+    assert!(status.success());
+    let content = fs::read_to_string(dir.join("2024-03.txt"))?;
+    fs::remove_dir_all(&dir)?;
+    assert!(!content.contains('\u{1b}'));
+    assert!(content.contains("10 11 12 13 14 15 16"));
+    Ok(())
+}
+
+// --------------------------------------------------
+#[test]
+fn dies_from_day_beyond_month() -> Result<()> {
+    calr()?
+        .args(["--from-day", "31", "-m", "2", "2024"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            r#"--from-day "31" exceeds the 29 days of 2024-02"#,
+        ));
+    Ok(())
+}